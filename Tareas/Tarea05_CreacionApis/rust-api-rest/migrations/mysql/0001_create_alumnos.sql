@@ -0,0 +1,20 @@
+-- Tabla de alumnos (esquema original de scripts/setup_database.sql)
+-- IF NOT EXISTS permite adoptar bases creadas antes a mano con ese script
+CREATE TABLE IF NOT EXISTS alumnos (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    nombre VARCHAR(100) NOT NULL,
+    apellido VARCHAR(100) NOT NULL,
+    email VARCHAR(150) UNIQUE NOT NULL,
+    edad INT NOT NULL CHECK (edad >= 16 AND edad <= 65),
+    carrera VARCHAR(100) NOT NULL,
+    semestre INT NOT NULL CHECK (semestre >= 1 AND semestre <= 10),
+    promedio DOUBLE DEFAULT 0.00 CHECK (promedio >= 0.00 AND promedio <= 10.00),
+    activo BOOLEAN DEFAULT TRUE,
+    fecha_registro DATETIME DEFAULT CURRENT_TIMESTAMP,
+    fecha_actualizacion DATETIME DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    INDEX idx_email (email),
+    INDEX idx_carrera (carrera),
+    INDEX idx_activo (activo),
+    INDEX idx_fecha_registro (fecha_registro)
+) CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci;
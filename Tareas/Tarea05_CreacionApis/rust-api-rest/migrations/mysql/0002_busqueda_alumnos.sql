@@ -0,0 +1,3 @@
+-- Búsqueda de texto completo (?search=) sobre nombre, apellido, email y carrera.
+-- La collation utf8mb4_unicode_ci de la tabla ya ignora acentos y mayúsculas.
+ALTER TABLE alumnos ADD FULLTEXT INDEX idx_busqueda (nombre, apellido, email, carrera);
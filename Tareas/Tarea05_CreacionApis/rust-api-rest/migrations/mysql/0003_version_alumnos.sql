@@ -0,0 +1,3 @@
+-- Versión de cada alumno para el control de concurrencia optimista (ETag / If-Match).
+-- Empieza en 1 y cada UPDATE la incrementa (ver database::update_query).
+ALTER TABLE alumnos ADD COLUMN version INT NOT NULL DEFAULT 1;
@@ -0,0 +1,17 @@
+-- promedio, activo y las fechas eran NULL-ables en 0001 (como en scripts/setup_database.sql),
+-- pero Alumno no los admite y los cursores no pueden guardar NULL. Se completan las filas
+-- existentes con los valores por defecto y se declaran NOT NULL, igual que en PostgreSQL
+-- y SQLite. Los CHECK de 0001 son de la tabla y se conservan.
+UPDATE alumnos SET promedio = 0.00 WHERE promedio IS NULL;
+UPDATE alumnos SET activo = TRUE WHERE activo IS NULL;
+UPDATE alumnos SET fecha_registro = CURRENT_TIMESTAMP WHERE fecha_registro IS NULL;
+UPDATE alumnos
+SET fecha_actualizacion = COALESCE(fecha_registro, CURRENT_TIMESTAMP)
+WHERE fecha_actualizacion IS NULL;
+
+ALTER TABLE alumnos
+    MODIFY promedio DOUBLE NOT NULL DEFAULT 0.00,
+    MODIFY activo BOOLEAN NOT NULL DEFAULT TRUE,
+    MODIFY fecha_registro DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    MODIFY fecha_actualizacion DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        ON UPDATE CURRENT_TIMESTAMP;
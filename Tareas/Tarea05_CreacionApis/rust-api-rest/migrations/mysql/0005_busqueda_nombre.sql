@@ -0,0 +1,3 @@
+-- Búsqueda por nombre de los candidatos a duplicado (ver Identity::candidates_search).
+-- MATCH necesita un índice FULLTEXT con exactamente las columnas que busca.
+ALTER TABLE alumnos ADD FULLTEXT INDEX idx_busqueda_nombre (nombre, apellido);
@@ -0,0 +1,37 @@
+-- Tabla de alumnos para PostgreSQL, equivalente a migrations/mysql/0001_create_alumnos.sql
+
+-- Crear tabla de alumnos
+-- TIMESTAMP(0) conserva la precisión de segundos del DATETIME de MySQL
+CREATE TABLE IF NOT EXISTS alumnos (
+    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    nombre VARCHAR(100) NOT NULL,
+    apellido VARCHAR(100) NOT NULL,
+    email VARCHAR(150) NOT NULL,
+    edad INTEGER NOT NULL CHECK (edad >= 16 AND edad <= 65),
+    carrera VARCHAR(100) NOT NULL,
+    semestre INTEGER NOT NULL CHECK (semestre >= 1 AND semestre <= 10),
+    promedio DOUBLE PRECISION NOT NULL DEFAULT 0.00 CHECK (promedio >= 0.00 AND promedio <= 10.00),
+    activo BOOLEAN NOT NULL DEFAULT TRUE,
+    fecha_registro TIMESTAMP(0) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    fecha_actualizacion TIMESTAMP(0) WITH TIME ZONE NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Índices sobre LOWER(...) replican la comparación sin mayúsculas de utf8mb4_unicode_ci
+CREATE UNIQUE INDEX IF NOT EXISTS idx_email ON alumnos (LOWER(email));
+CREATE INDEX IF NOT EXISTS idx_carrera ON alumnos (LOWER(carrera));
+CREATE INDEX IF NOT EXISTS idx_activo ON alumnos (activo);
+CREATE INDEX IF NOT EXISTS idx_fecha_registro ON alumnos (fecha_registro);
+
+-- Equivalente a ON UPDATE CURRENT_TIMESTAMP de MySQL
+CREATE OR REPLACE FUNCTION alumnos_fecha_actualizacion() RETURNS TRIGGER AS $$
+BEGIN
+    NEW.fecha_actualizacion = CURRENT_TIMESTAMP;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_alumnos_fecha_actualizacion ON alumnos;
+CREATE TRIGGER trg_alumnos_fecha_actualizacion
+BEFORE UPDATE ON alumnos
+FOR EACH ROW
+EXECUTE FUNCTION alumnos_fecha_actualizacion();
@@ -0,0 +1,15 @@
+-- Búsqueda de texto completo (?search=), equivalente a migrations/mysql/0002_busqueda_alumnos.sql
+
+-- unaccent quita los acentos, como la collation utf8mb4_unicode_ci de MySQL
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- Documento de búsqueda del alumno; IMMUTABLE permite indexarlo. Los signos del email
+-- se cambian por espacios para que usuario y dominio se busquen como palabras sueltas.
+CREATE OR REPLACE FUNCTION alumnos_documento(nombre TEXT, apellido TEXT, email TEXT, carrera TEXT)
+RETURNS tsvector AS $$
+    SELECT to_tsvector('simple', unaccent('unaccent'::regdictionary,
+        nombre || ' ' || apellido || ' ' || translate(email, '@._-+', '     ') || ' ' || carrera))
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE INDEX IF NOT EXISTS idx_busqueda ON alumnos
+    USING GIN (alumnos_documento(nombre, apellido, email, carrera));
@@ -0,0 +1,11 @@
+-- Búsqueda por nombre de los candidatos a duplicado (ver Identity::candidates_search),
+-- equivalente a migrations/mysql/0005_busqueda_nombre.sql
+
+-- Documento con solo el nombre y el apellido, sin acentos como alumnos_documento
+CREATE OR REPLACE FUNCTION alumnos_nombre(nombre TEXT, apellido TEXT)
+RETURNS tsvector AS $$
+    SELECT to_tsvector('simple', unaccent('unaccent'::regdictionary, nombre || ' ' || apellido))
+$$ LANGUAGE sql IMMUTABLE;
+
+CREATE INDEX IF NOT EXISTS idx_busqueda_nombre ON alumnos
+    USING GIN (alumnos_nombre(nombre, apellido));
@@ -0,0 +1,31 @@
+-- Tabla de alumnos para SQLite, equivalente a migrations/mysql/0001_create_alumnos.sql
+
+-- Crear tabla de alumnos
+-- COLLATE NOCASE replica la comparación sin mayúsculas de utf8mb4_unicode_ci
+CREATE TABLE IF NOT EXISTS alumnos (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    nombre VARCHAR(100) NOT NULL,
+    apellido VARCHAR(100) NOT NULL,
+    email VARCHAR(150) NOT NULL UNIQUE COLLATE NOCASE,
+    edad INTEGER NOT NULL CHECK (edad >= 16 AND edad <= 65),
+    carrera VARCHAR(100) NOT NULL COLLATE NOCASE,
+    semestre INTEGER NOT NULL CHECK (semestre >= 1 AND semestre <= 10),
+    promedio DOUBLE NOT NULL DEFAULT 0.00 CHECK (promedio >= 0.00 AND promedio <= 10.00),
+    activo BOOLEAN NOT NULL DEFAULT TRUE,
+    fecha_registro DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    fecha_actualizacion DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_email ON alumnos (email);
+CREATE INDEX IF NOT EXISTS idx_carrera ON alumnos (carrera);
+CREATE INDEX IF NOT EXISTS idx_activo ON alumnos (activo);
+CREATE INDEX IF NOT EXISTS idx_fecha_registro ON alumnos (fecha_registro);
+
+-- Equivalente a ON UPDATE CURRENT_TIMESTAMP de MySQL
+CREATE TRIGGER IF NOT EXISTS trg_alumnos_fecha_actualizacion
+AFTER UPDATE ON alumnos
+FOR EACH ROW
+WHEN NEW.fecha_actualizacion = OLD.fecha_actualizacion
+BEGIN
+    UPDATE alumnos SET fecha_actualizacion = CURRENT_TIMESTAMP WHERE id = NEW.id;
+END;
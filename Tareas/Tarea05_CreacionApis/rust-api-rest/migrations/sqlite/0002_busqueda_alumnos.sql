@@ -0,0 +1,37 @@
+-- Búsqueda de texto completo (?search=), equivalente a migrations/mysql/0002_busqueda_alumnos.sql
+
+-- Índice FTS5 sobre la tabla alumnos (sin copiar los datos); remove_diacritics quita
+-- los acentos y unicode61 separa palabras en los signos, también en los emails
+CREATE VIRTUAL TABLE IF NOT EXISTS alumnos_busqueda USING fts5(
+    nombre, apellido, email, carrera,
+    content = 'alumnos',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- Indexar los alumnos existentes
+INSERT INTO alumnos_busqueda (alumnos_busqueda) VALUES ('rebuild');
+
+-- Mantener el índice sincronizado con la tabla
+CREATE TRIGGER IF NOT EXISTS trg_alumnos_busqueda_insert
+AFTER INSERT ON alumnos
+BEGIN
+    INSERT INTO alumnos_busqueda (rowid, nombre, apellido, email, carrera)
+    VALUES (NEW.id, NEW.nombre, NEW.apellido, NEW.email, NEW.carrera);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_alumnos_busqueda_delete
+AFTER DELETE ON alumnos
+BEGIN
+    INSERT INTO alumnos_busqueda (alumnos_busqueda, rowid, nombre, apellido, email, carrera)
+    VALUES ('delete', OLD.id, OLD.nombre, OLD.apellido, OLD.email, OLD.carrera);
+END;
+
+CREATE TRIGGER IF NOT EXISTS trg_alumnos_busqueda_update
+AFTER UPDATE OF nombre, apellido, email, carrera ON alumnos
+BEGIN
+    INSERT INTO alumnos_busqueda (alumnos_busqueda, rowid, nombre, apellido, email, carrera)
+    VALUES ('delete', OLD.id, OLD.nombre, OLD.apellido, OLD.email, OLD.carrera);
+    INSERT INTO alumnos_busqueda (rowid, nombre, apellido, email, carrera)
+    VALUES (NEW.id, NEW.nombre, NEW.apellido, NEW.email, NEW.carrera);
+END;
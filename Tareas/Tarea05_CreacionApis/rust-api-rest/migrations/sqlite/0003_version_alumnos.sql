@@ -0,0 +1,2 @@
+-- Versión de cada alumno, equivalente a migrations/mysql/0003_version_alumnos.sql
+ALTER TABLE alumnos ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
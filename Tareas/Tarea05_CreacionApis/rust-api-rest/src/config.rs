@@ -0,0 +1,445 @@
+use std::{env, time::Duration};
+
+use ::config::{Config, ConfigBuilder, Environment, File, FileFormat, Map, builder::DefaultState};
+use axum::http::HeaderValue;
+use serde::Deserialize;
+use thiserror::Error;
+use tracing_subscriber::EnvFilter;
+
+use crate::database::RetryPolicy;
+
+/// Archivo de configuración que se lee si existe (relativo al directorio de trabajo)
+pub const DEFAULT_CONFIG_FILE: &str = "config/default.toml";
+
+/// Variable de entorno con la ruta de otro archivo de configuración (debe existir)
+pub const CONFIG_FILE_ENV: &str = "APP_CONFIG";
+
+/// Prefijo de las variables que sobrescriben el archivo, p. ej. `APP_SERVER__PORT=8080`
+pub const ENV_PREFIX: &str = "APP";
+
+/// Secciones de `Settings`; las variables `APP_*` de otras secciones se ignoran
+const SECTIONS: [&str; 8] = [
+    "server",
+    "database",
+    "cors",
+    "log",
+    "otel",
+    "pagination",
+    "concurrency",
+    "duplicates",
+];
+
+/// Errores al cargar la configuración; la aplicación no arranca con ninguno de ellos
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("No se pudo leer la configuración: {0}")]
+    Load(#[from] ::config::ConfigError),
+
+    #[error("Configuración inválida: {}", .0.join("; "))]
+    Invalid(Vec<String>),
+}
+
+/// Configuración completa de la aplicación; una sección desconocida del archivo (p. ej.
+/// `[databse]`) es un error, igual que una clave desconocida dentro de una sección
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub server: ServerSettings,
+    pub database: DatabaseSettings,
+    pub cors: CorsSettings,
+    pub log: LogSettings,
+    pub otel: OtelSettings,
+    pub pagination: PaginationSettings,
+    pub concurrency: ConcurrencySettings,
+    pub duplicates: DuplicatesSettings,
+}
+
+/// Dirección en la que escucha el servidor HTTP
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSettings {
+    pub host: String,
+    pub port: u16,
+    /// Segundos que se sigue atendiendo al apagar, con readiness en 503, antes de dejar
+    /// de aceptar conexiones (para que el balanceador saque la instancia)
+    pub shutdown_delay_secs: u64,
+    /// Segundos de espera por las requests en curso y el cierre del pool al apagar
+    pub shutdown_timeout_secs: u64,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 3000,
+            shutdown_delay_secs: 5,
+            shutdown_timeout_secs: 30,
+        }
+    }
+}
+
+impl ServerSettings {
+    pub fn shutdown_delay(&self) -> Duration {
+        Duration::from_secs(self.shutdown_delay_secs)
+    }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.shutdown_timeout_secs)
+    }
+}
+
+/// Conexión a la base de datos y tamaño del pool
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseSettings {
+    /// `mysql://`, `postgres://`, `sqlite:` o `memory://`
+    pub url: String,
+    /// Aplicar las migraciones pendientes al iniciar
+    pub auto_migrate: bool,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// Tiempo máximo de espera por una conexión libre del pool
+    pub acquire_timeout_secs: u64,
+    /// Segundos que una conexión puede estar inactiva antes de cerrarse (0 = sin límite)
+    pub idle_timeout_secs: u64,
+    /// Vida máxima de una conexión en segundos (0 = sin límite)
+    pub max_lifetime_secs: u64,
+    /// Tiempo total reintentando la conexión inicial (0 = un solo intento)
+    pub connect_deadline_secs: u64,
+    /// Espera antes del primer reintento; se duplica hasta `connect_backoff_max_ms`
+    pub connect_backoff_initial_ms: u64,
+    pub connect_backoff_max_ms: u64,
+    /// Tiempo máximo del `SELECT 1` que hace `/health/ready`
+    pub ping_timeout_ms: u64,
+}
+
+impl DatabaseSettings {
+    /// Configuración por defecto apuntando a otra URL
+    pub fn with_url(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn acquire_timeout(&self) -> Duration {
+        Duration::from_secs(self.acquire_timeout_secs)
+    }
+
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        (self.idle_timeout_secs > 0).then(|| Duration::from_secs(self.idle_timeout_secs))
+    }
+
+    pub fn max_lifetime(&self) -> Option<Duration> {
+        (self.max_lifetime_secs > 0).then(|| Duration::from_secs(self.max_lifetime_secs))
+    }
+
+    pub fn ping_timeout(&self) -> Duration {
+        Duration::from_millis(self.ping_timeout_ms)
+    }
+
+    /// Reintentos de la conexión inicial, para motores que tardan en aceptar conexiones
+    pub fn connect_retry(&self) -> RetryPolicy {
+        RetryPolicy {
+            deadline: Duration::from_secs(self.connect_deadline_secs),
+            initial_backoff: Duration::from_millis(self.connect_backoff_initial_ms),
+            max_backoff: Duration::from_millis(self.connect_backoff_max_ms),
+        }
+    }
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        Self {
+            url: "mysql://root:@localhost:3306/alumnos_db".to_string(),
+            auto_migrate: true,
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout_secs: 30,
+            idle_timeout_secs: 600,
+            max_lifetime_secs: 1800,
+            connect_deadline_secs: 60,
+            connect_backoff_initial_ms: 250,
+            connect_backoff_max_ms: 5000,
+            ping_timeout_ms: 2000,
+        }
+    }
+}
+
+/// Orígenes permitidos por CORS
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsSettings {
+    pub allowed_origins: Vec<String>,
+}
+
+impl CorsSettings {
+    /// Orígenes como valores de cabecera (ya verificados por `Settings::validate`)
+    pub fn origins(&self) -> Vec<HeaderValue> {
+        self.allowed_origins
+            .iter()
+            .filter_map(|origin| origin.parse().ok())
+            .collect()
+    }
+}
+
+impl Default for CorsSettings {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![
+                "http://localhost:3000".to_string(),
+                "http://127.0.0.1:3000".to_string(),
+            ],
+        }
+    }
+}
+
+/// Formato de salida de los logs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Texto legible para desarrollo
+    #[default]
+    Text,
+    /// Una línea JSON por evento, para agregadores de logs
+    Json,
+}
+
+/// Formato y nivel de los logs; `RUST_LOG` tiene prioridad sobre `filter`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogSettings {
+    pub format: LogFormat,
+    pub filter: String,
+    /// Parámetros de query cuyo valor se reemplaza por `[REDACTED]` en los logs de requests
+    pub redact: Vec<String>,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Text,
+            filter: "rust_api_rest=debug,tower_http=debug".to_string(),
+            // `search` puede ser un email y `email_dominio` identifica a la institución
+            redact: vec!["search".to_string(), "email_dominio".to_string()],
+        }
+    }
+}
+
+/// Codificación del cuerpo de las exportaciones OTLP/HTTP
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum OtlpProtocol {
+    /// Protobuf binario, el formato por defecto de los collectors
+    #[default]
+    #[serde(rename = "http/protobuf")]
+    Protobuf,
+    #[serde(rename = "http/json")]
+    Json,
+}
+
+/// Exportación de trazas a un collector OpenTelemetry por OTLP/HTTP
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OtelSettings {
+    pub enabled: bool,
+    /// URL completa del endpoint de trazas del collector
+    pub endpoint: String,
+    pub protocol: OtlpProtocol,
+    /// Nombre del servicio en las trazas (`service.name`)
+    pub service_name: String,
+    /// Segundos máximos de cada envío al collector
+    pub export_timeout_secs: u64,
+}
+
+impl Default for OtelSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4318/v1/traces".to_string(),
+            protocol: OtlpProtocol::Protobuf,
+            service_name: "rust-api-rest".to_string(),
+            export_timeout_secs: 10,
+        }
+    }
+}
+
+impl OtelSettings {
+    pub fn export_timeout(&self) -> Duration {
+        Duration::from_secs(self.export_timeout_secs)
+    }
+}
+
+/// Límites de paginación del listado de alumnos
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PaginationSettings {
+    /// Elementos por página cuando no se envía `limit`
+    pub default_limit: i64,
+    /// Máximo de elementos por página aceptado
+    pub max_limit: i64,
+}
+
+impl Default for PaginationSettings {
+    fn default() -> Self {
+        Self {
+            default_limit: 10,
+            max_limit: 100,
+        }
+    }
+}
+
+/// Control de concurrencia optimista de las escrituras sobre un alumno
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConcurrencySettings {
+    /// Rechazar con 428 los PUT, PATCH y DELETE que no envían `If-Match`
+    pub require_if_match: bool,
+}
+
+/// Detección de posibles duplicados al crear un alumno
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DuplicatesSettings {
+    /// Rechazar con 409 la creación de un alumno parecido a otro (salvo con `force=true`);
+    /// si no, se crea y los parecidos se informan en la respuesta
+    pub block_on_create: bool,
+}
+
+impl Settings {
+    /// Carga la configuración en orden de prioridad creciente: valores por defecto,
+    /// `config/default.toml` (o el archivo de `APP_CONFIG`), variables `APP_*`
+    /// y las variables históricas `DATABASE_URL`, `SERVER_HOST`, `SERVER_PORT`
+    /// y `DATABASE_AUTO_MIGRATE`.
+    pub fn load() -> Result<Self, SettingsError> {
+        let file = match env::var(CONFIG_FILE_ENV) {
+            Ok(path) => File::with_name(&path).required(true),
+            Err(_) => File::with_name(DEFAULT_CONFIG_FILE).required(false),
+        };
+
+        // Solo se leen las variables `APP_<SECCION>__<CAMPO>` de una sección conocida: otras
+        // con el mismo prefijo (APP_CONFIG o las que Kubernetes define para un Service
+        // `app`, como APP_SERVICE_HOST y APP_PORT) no son claves de la configuración
+        let variables: Map<String, String> = env::vars()
+            .filter(|(name, _)| {
+                name.strip_prefix(ENV_PREFIX)
+                    .and_then(|name| name.strip_prefix('_'))
+                    .and_then(|name| name.split_once("__"))
+                    .is_some_and(|(section, _)| SECTIONS.contains(&section.to_lowercase().as_str()))
+            })
+            .collect();
+
+        let builder = Config::builder()
+            .add_source(file)
+            .add_source(
+                Environment::with_prefix(ENV_PREFIX)
+                    .source(Some(variables))
+                    .prefix_separator("_")
+                    .separator("__")
+                    .list_separator(",")
+                    .with_list_parse_key("cors.allowed_origins")
+                    .with_list_parse_key("log.redact")
+                    .try_parsing(true),
+            )
+            .set_override_option("database.url", env::var("DATABASE_URL").ok())?
+            .set_override_option("server.host", env::var("SERVER_HOST").ok())?
+            .set_override_option("server.port", env::var("SERVER_PORT").ok())?
+            .set_override_option(
+                "database.auto_migrate",
+                env::var("DATABASE_AUTO_MIGRATE").ok(),
+            )?;
+
+        Self::build(builder)
+    }
+
+    /// Carga la configuración solo desde un documento TOML, sin variables de entorno
+    pub fn from_toml(contents: &str) -> Result<Self, SettingsError> {
+        Self::build(Config::builder().add_source(File::from_str(contents, FileFormat::Toml)))
+    }
+
+    fn build(builder: ConfigBuilder<DefaultState>) -> Result<Self, SettingsError> {
+        let settings: Settings = builder.build()?.try_deserialize()?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// Verifica las reglas que el tipo de cada campo no expresa y reporta todas juntas
+    pub fn validate(&self) -> Result<(), SettingsError> {
+        let mut errors = Vec::new();
+
+        if self.server.host.trim().is_empty() {
+            errors.push("server.host no puede estar vacío".to_string());
+        }
+
+        if self.database.url.trim().is_empty() {
+            errors.push("database.url no puede estar vacío".to_string());
+        }
+        if self.database.max_connections == 0 {
+            errors.push("database.max_connections debe ser al menos 1".to_string());
+        }
+        if self.database.min_connections > self.database.max_connections {
+            errors.push(format!(
+                "database.min_connections ({}) no puede ser mayor que database.max_connections ({})",
+                self.database.min_connections, self.database.max_connections
+            ));
+        }
+        if self.database.acquire_timeout_secs == 0 {
+            errors.push("database.acquire_timeout_secs debe ser al menos 1".to_string());
+        }
+        if self.database.ping_timeout_ms == 0 {
+            errors.push("database.ping_timeout_ms debe ser al menos 1".to_string());
+        }
+        if self.database.connect_backoff_initial_ms == 0 {
+            errors.push("database.connect_backoff_initial_ms debe ser al menos 1".to_string());
+        }
+        if self.database.connect_backoff_max_ms < self.database.connect_backoff_initial_ms {
+            errors.push(format!(
+                "database.connect_backoff_max_ms ({}) no puede ser menor que database.connect_backoff_initial_ms ({})",
+                self.database.connect_backoff_max_ms, self.database.connect_backoff_initial_ms
+            ));
+        }
+
+        for origin in &self.cors.allowed_origins {
+            let valid_scheme = origin.starts_with("http://") || origin.starts_with("https://");
+            if !valid_scheme || origin.parse::<HeaderValue>().is_err() {
+                errors.push(format!(
+                    "cors.allowed_origins: '{origin}' no es un origen válido (http:// o https://)"
+                ));
+            }
+        }
+
+        if let Err(e) = EnvFilter::try_new(&self.log.filter) {
+            errors.push(format!("log.filter inválido: {e}"));
+        }
+
+        if self.otel.enabled {
+            let endpoint = &self.otel.endpoint;
+            if !(endpoint.starts_with("http://") || endpoint.starts_with("https://")) {
+                errors.push(format!(
+                    "otel.endpoint: '{endpoint}' no es una URL válida (http:// o https://)"
+                ));
+            }
+            if self.otel.service_name.trim().is_empty() {
+                errors.push("otel.service_name no puede estar vacío".to_string());
+            }
+            if self.otel.export_timeout_secs == 0 {
+                errors.push("otel.export_timeout_secs debe ser al menos 1".to_string());
+            }
+        }
+
+        if self.pagination.default_limit < 1 {
+            errors.push("pagination.default_limit debe ser al menos 1".to_string());
+        }
+        if self.pagination.max_limit < self.pagination.default_limit {
+            errors.push(format!(
+                "pagination.max_limit ({}) no puede ser menor que pagination.default_limit ({})",
+                self.pagination.max_limit, self.pagination.default_limit
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SettingsError::Invalid(errors))
+        }
+    }
+}
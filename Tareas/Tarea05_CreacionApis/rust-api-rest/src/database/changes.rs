@@ -0,0 +1,69 @@
+use sqlx::{Database, Encode, QueryBuilder, Type};
+
+use crate::models::UpdateAlumnoRequest;
+
+/// Construye el UPDATE de un alumno con solo los campos presentes en el request; cada
+/// UPDATE incrementa la versión. El llamador debe verificar antes que `changes` no esté
+/// vacío.
+pub fn update_query<DB>(id: i32, changes: &UpdateAlumnoRequest) -> QueryBuilder<'static, DB>
+where
+    DB: Database,
+    String: Encode<'static, DB> + Type<DB>,
+    i32: Encode<'static, DB> + Type<DB>,
+    f64: Encode<'static, DB> + Type<DB>,
+    bool: Encode<'static, DB> + Type<DB>,
+{
+    let mut builder = QueryBuilder::new("UPDATE alumnos SET ");
+    let mut fields = builder.separated(", ");
+
+    if let Some(nombre) = &changes.nombre {
+        fields
+            .push("nombre = ")
+            .push_bind_unseparated(nombre.clone());
+    }
+    if let Some(apellido) = &changes.apellido {
+        fields
+            .push("apellido = ")
+            .push_bind_unseparated(apellido.clone());
+    }
+    if let Some(email) = &changes.email {
+        fields.push("email = ").push_bind_unseparated(email.clone());
+    }
+    if let Some(edad) = changes.edad {
+        fields.push("edad = ").push_bind_unseparated(edad);
+    }
+    if let Some(carrera) = &changes.carrera {
+        fields
+            .push("carrera = ")
+            .push_bind_unseparated(carrera.clone());
+    }
+    if let Some(semestre) = changes.semestre {
+        fields.push("semestre = ").push_bind_unseparated(semestre);
+    }
+    if let Some(promedio) = changes.promedio {
+        fields.push("promedio = ").push_bind_unseparated(promedio);
+    }
+    if let Some(activo) = changes.activo {
+        fields.push("activo = ").push_bind_unseparated(activo);
+    }
+
+    fields.push("version = version + 1");
+
+    builder.push(" WHERE id = ").push_bind(id);
+    builder
+}
+
+/// Construye el DELETE de un alumno; con `version` solo se elimina si el alumno sigue en
+/// esa versión
+pub fn delete_query<DB>(id: i32, version: Option<i32>) -> QueryBuilder<'static, DB>
+where
+    DB: Database,
+    i32: Encode<'static, DB> + Type<DB>,
+{
+    let mut builder = QueryBuilder::new("DELETE FROM alumnos WHERE id = ");
+    builder.push_bind(id);
+    if let Some(version) = version {
+        builder.push(" AND version = ").push_bind(version);
+    }
+    builder
+}
@@ -0,0 +1,136 @@
+use crate::{database::SortValue, error::AppError, models::PartialAlumno};
+
+/// Columnas de `alumnos` que el cliente puede nombrar en `?sort=` y `?fields=`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlumnoColumn {
+    Id,
+    Nombre,
+    Apellido,
+    Email,
+    Edad,
+    Carrera,
+    Semestre,
+    Promedio,
+    Activo,
+    FechaRegistro,
+    FechaActualizacion,
+    Version,
+    /// Relevancia calculada por `search`: no es una columna de la tabla, solo se puede
+    /// usar en `?sort=` al buscar y por eso no está en `ALL`
+    Score,
+}
+
+impl AlumnoColumn {
+    pub const ALL: [AlumnoColumn; 12] = [
+        AlumnoColumn::Id,
+        AlumnoColumn::Nombre,
+        AlumnoColumn::Apellido,
+        AlumnoColumn::Email,
+        AlumnoColumn::Edad,
+        AlumnoColumn::Carrera,
+        AlumnoColumn::Semestre,
+        AlumnoColumn::Promedio,
+        AlumnoColumn::Activo,
+        AlumnoColumn::FechaRegistro,
+        AlumnoColumn::FechaActualizacion,
+        AlumnoColumn::Version,
+    ];
+
+    /// Nombre de la columna; también es el nombre aceptado en los query params
+    pub fn column(self) -> &'static str {
+        match self {
+            AlumnoColumn::Id => "id",
+            AlumnoColumn::Nombre => "nombre",
+            AlumnoColumn::Apellido => "apellido",
+            AlumnoColumn::Email => "email",
+            AlumnoColumn::Edad => "edad",
+            AlumnoColumn::Carrera => "carrera",
+            AlumnoColumn::Semestre => "semestre",
+            AlumnoColumn::Promedio => "promedio",
+            AlumnoColumn::Activo => "activo",
+            AlumnoColumn::FechaRegistro => "fecha_registro",
+            AlumnoColumn::FechaActualizacion => "fecha_actualizacion",
+            AlumnoColumn::Version => "version",
+            AlumnoColumn::Score => "score",
+        }
+    }
+
+    /// Busca la columna por nombre; `param` es el query param que la nombró, para el 400
+    pub fn parse(column: &str, param: &str) -> Result<Self, AppError> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.column() == column)
+            .ok_or_else(|| {
+                let allowed: Vec<_> = Self::ALL.iter().map(|field| field.column()).collect();
+                AppError::BadRequest(format!(
+                    "Campo '{column}' no permitido en {param}. Campos permitidos: {}",
+                    allowed.join(", ")
+                ))
+            })
+    }
+
+    /// Valor de la columna en un alumno; `None` si no se seleccionó
+    pub fn value(self, alumno: &PartialAlumno) -> Option<SortValue> {
+        match self {
+            AlumnoColumn::Id => alumno.id.map(SortValue::Int),
+            AlumnoColumn::Nombre => alumno.nombre.clone().map(SortValue::Text),
+            AlumnoColumn::Apellido => alumno.apellido.clone().map(SortValue::Text),
+            AlumnoColumn::Email => alumno.email.clone().map(SortValue::Text),
+            AlumnoColumn::Edad => alumno.edad.map(SortValue::Int),
+            AlumnoColumn::Carrera => alumno.carrera.clone().map(SortValue::Text),
+            AlumnoColumn::Semestre => alumno.semestre.map(SortValue::Int),
+            AlumnoColumn::Promedio => alumno.promedio.map(SortValue::Float),
+            AlumnoColumn::Activo => alumno.activo.map(SortValue::Bool),
+            AlumnoColumn::FechaRegistro => alumno.fecha_registro.map(SortValue::Fecha),
+            AlumnoColumn::FechaActualizacion => alumno.fecha_actualizacion.map(SortValue::Fecha),
+            AlumnoColumn::Version => alumno.version.map(SortValue::Int),
+            AlumnoColumn::Score => alumno.score.map(SortValue::Float),
+        }
+    }
+
+    /// Quita la columna del alumno para que no se serialice
+    pub fn clear(self, alumno: &mut PartialAlumno) {
+        match self {
+            AlumnoColumn::Id => alumno.id = None,
+            AlumnoColumn::Nombre => alumno.nombre = None,
+            AlumnoColumn::Apellido => alumno.apellido = None,
+            AlumnoColumn::Email => alumno.email = None,
+            AlumnoColumn::Edad => alumno.edad = None,
+            AlumnoColumn::Carrera => alumno.carrera = None,
+            AlumnoColumn::Semestre => alumno.semestre = None,
+            AlumnoColumn::Promedio => alumno.promedio = None,
+            AlumnoColumn::Activo => alumno.activo = None,
+            AlumnoColumn::FechaRegistro => alumno.fecha_registro = None,
+            AlumnoColumn::FechaActualizacion => alumno.fecha_actualizacion = None,
+            AlumnoColumn::Version => alumno.version = None,
+            AlumnoColumn::Score => alumno.score = None,
+        }
+    }
+
+    /// Indica si un valor (p. ej. leído de un cursor) tiene el tipo de la columna
+    pub fn accepts(self, value: &SortValue) -> bool {
+        matches!(
+            (self, value),
+            (
+                AlumnoColumn::Id
+                    | AlumnoColumn::Edad
+                    | AlumnoColumn::Semestre
+                    | AlumnoColumn::Version,
+                SortValue::Int(_)
+            ) | (
+                AlumnoColumn::Nombre
+                    | AlumnoColumn::Apellido
+                    | AlumnoColumn::Email
+                    | AlumnoColumn::Carrera,
+                SortValue::Text(_)
+            ) | (
+                AlumnoColumn::Promedio | AlumnoColumn::Score,
+                SortValue::Float(_)
+            ) | (AlumnoColumn::Activo, SortValue::Bool(_))
+                | (
+                    AlumnoColumn::FechaRegistro | AlumnoColumn::FechaActualizacion,
+                    SortValue::Fecha(_)
+                )
+        )
+    }
+}
@@ -1,20 +1,113 @@
 use anyhow::Result;
-use sqlx::{MySql, MySqlPool, Pool};
+use sqlx::{Database, MySql, Pool, mysql::MySqlConnectOptions, pool::PoolOptions};
 
+use super::MYSQL_MIGRATOR;
+#[cfg(feature = "postgres")]
+use super::POSTGRES_MIGRATOR;
+#[cfg(feature = "sqlite")]
+use super::SQLITE_MIGRATOR;
+use super::retry_with_backoff;
+use crate::config::DatabaseSettings;
+#[cfg(feature = "postgres")]
+use sqlx::{Postgres, postgres::PgConnectOptions};
+#[cfg(feature = "sqlite")]
+use sqlx::{Sqlite, sqlite::SqliteConnectOptions};
 
 pub type DbPool = Pool<MySql>;
 
-/// Crea un pool de conexiones a MySQL con verificación de conectividad
-pub async fn create_connection_pool(database_url: &str) -> Result<DbPool> {
-    // Crear pool con configuración desde URL
-    let pool = MySqlPool::connect_with(
-        database_url.parse()?, // Parsear URL de conexión
-    )
+#[cfg(feature = "sqlite")]
+pub type SqliteDbPool = Pool<Sqlite>;
+
+#[cfg(feature = "postgres")]
+pub type PgDbPool = Pool<Postgres>;
+
+/// Opciones del pool comunes a todos los motores, tomadas de la configuración
+fn pool_options<DB: Database>(settings: &DatabaseSettings) -> PoolOptions<DB> {
+    PoolOptions::new()
+        .max_connections(settings.max_connections)
+        .min_connections(settings.min_connections)
+        .acquire_timeout(settings.acquire_timeout())
+        .idle_timeout(settings.idle_timeout())
+        .max_lifetime(settings.max_lifetime())
+}
+
+/// Crea un pool de conexiones a MySQL con verificación de conectividad, reintentando
+/// con espera exponencial mientras el servidor no acepte conexiones.
+/// Con `migrate` aplica las migraciones pendientes antes de retornar el pool.
+pub async fn create_connection_pool(settings: &DatabaseSettings, migrate: bool) -> Result<DbPool> {
+    // Crear pool con la URL y los límites de la configuración
+    let options = settings.url.parse::<MySqlConnectOptions>()?;
+    let pool = retry_with_backoff(&settings.connect_retry(), "Conexión a MySQL", || {
+        pool_options::<MySql>(settings).connect_with(options.clone())
+    })
     .await?;
 
-    // Verificar conectividad obteniendo una conexión del pool
-    let _connection = pool.acquire().await?;
+    // Verificar conectividad obteniendo una conexión del pool; se devuelve enseguida para
+    // que el migrador la pueda usar aunque el pool tenga una sola conexión
+    drop(pool.acquire().await?);
     tracing::info!("✅ Conexión a MySQL establecida correctamente");
 
+    if migrate {
+        MYSQL_MIGRATOR.run(&pool).await?;
+        tracing::info!("📦 Migraciones de MySQL aplicadas");
+    }
+
+    Ok(pool)
+}
+
+/// Crea un pool SQLite (archivo o `sqlite::memory:`).
+/// Con `migrate` aplica las migraciones pendientes antes de retornar el pool.
+#[cfg(feature = "sqlite")]
+pub async fn create_sqlite_pool(
+    settings: &DatabaseSettings,
+    migrate: bool,
+) -> Result<SqliteDbPool> {
+    let database_url = settings.url.as_str();
+    let options = database_url
+        .parse::<SqliteConnectOptions>()?
+        .create_if_missing(true);
+
+    // Cada conexión a una base en memoria ve una base distinta,
+    // así que en ese caso el pool mantiene una única conexión viva
+    let in_memory = database_url.contains(":memory:") || database_url.contains("mode=memory");
+    let pool_options = if in_memory {
+        pool_options::<Sqlite>(settings)
+            .max_connections(1)
+            .min_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+    } else {
+        pool_options::<Sqlite>(settings)
+    };
+
+    let pool = pool_options.connect_with(options).await?;
+
+    tracing::info!("✅ Conexión a SQLite establecida correctamente");
+
+    if migrate {
+        SQLITE_MIGRATOR.run(&pool).await?;
+        tracing::info!("📦 Migraciones de SQLite aplicadas");
+    }
+
+    Ok(pool)
+}
+
+/// Crea un pool de conexiones a PostgreSQL, con los mismos reintentos que MySQL.
+/// Con `migrate` aplica las migraciones pendientes antes de retornar el pool.
+#[cfg(feature = "postgres")]
+pub async fn create_postgres_pool(settings: &DatabaseSettings, migrate: bool) -> Result<PgDbPool> {
+    let options = settings.url.parse::<PgConnectOptions>()?;
+    let pool = retry_with_backoff(&settings.connect_retry(), "Conexión a PostgreSQL", || {
+        pool_options::<Postgres>(settings).connect_with(options.clone())
+    })
+    .await?;
+
+    tracing::info!("✅ Conexión a PostgreSQL establecida correctamente");
+
+    if migrate {
+        POSTGRES_MIGRATOR.run(&pool).await?;
+        tracing::info!("📦 Migraciones de PostgreSQL aplicadas");
+    }
+
     Ok(pool)
-}
\ No newline at end of file
+}
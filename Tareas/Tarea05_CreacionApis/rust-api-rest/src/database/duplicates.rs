@@ -0,0 +1,236 @@
+use std::collections::BTreeSet;
+
+use strsim::normalized_damerau_levenshtein;
+
+use super::{SearchQuery, fold, words};
+use crate::models::{Alumno, CreateAlumnoRequest, DuplicateCandidate, DuplicatePair, Similarity};
+
+/// Similitud mínima por defecto para considerar que dos alumnos son la misma persona
+pub const DUPLICATE_THRESHOLD: f64 = 0.8;
+
+/// Peso de cada criterio en `Similarity::score`
+const PESO_NOMBRE: f64 = 0.6;
+const PESO_EMAIL: f64 = 0.3;
+const PESO_EDAD: f64 = 0.1;
+
+/// Letras del inicio de cada palabra del nombre que agrupan a los alumnos comparables
+const LARGO_PREFIJO: usize = 3;
+
+/// Alumnos que lee por vez el reporte de duplicados (`GET /api/alumnos/duplicados`)
+pub const DUPLICATE_BATCH: i64 = 100;
+
+/// Datos de una persona que se comparan para detectar duplicados
+#[derive(Debug, Clone, Copy)]
+pub struct Identity<'a> {
+    pub nombre: &'a str,
+    pub apellido: &'a str,
+    pub email: &'a str,
+    pub edad: i32,
+}
+
+impl<'a> From<&'a Alumno> for Identity<'a> {
+    fn from(alumno: &'a Alumno) -> Self {
+        Self {
+            nombre: &alumno.nombre,
+            apellido: &alumno.apellido,
+            email: &alumno.email,
+            edad: alumno.edad,
+        }
+    }
+}
+
+impl Identity<'_> {
+    /// Inicio normalizado de cada palabra del nombre y el apellido (`José Pérez` →
+    /// `jos`, `per`). Solo se comparan alumnos que comparten alguno: un error de tipeo
+    /// rara vez cae a la vez en el comienzo del nombre y en el del apellido.
+    pub fn blocking_keys(&self) -> Vec<String> {
+        let keys: BTreeSet<String> = words(&format!("{} {}", self.nombre, self.apellido))
+            .into_iter()
+            .map(|word| word.chars().take(LARGO_PREFIJO).collect())
+            .collect();
+        keys.into_iter().collect()
+    }
+
+    /// Búsqueda de los alumnos registrados que comparten alguna clave de `blocking_keys`.
+    /// Solo mira el nombre y el apellido; como busca prefijos, puede traer alumnos de
+    /// más (`jo` también encuentra a "José"), que luego descarta la similitud.
+    pub fn candidates_search(&self) -> SearchQuery {
+        SearchQuery::names_starting_with(self.blocking_keys())
+    }
+}
+
+impl<'a> From<&'a CreateAlumnoRequest> for Identity<'a> {
+    fn from(alumno: &'a CreateAlumnoRequest) -> Self {
+        Self {
+            nombre: &alumno.nombre,
+            apellido: &alumno.apellido,
+            email: &alumno.email,
+            edad: alumno.edad,
+        }
+    }
+}
+
+/// Compara dos personas. Los nombres se comparan sin acentos, mayúsculas ni signos por
+/// distancia de edición (una letra cambiada o dos transpuestas cuentan como un error), en
+/// los dos órdenes posibles de nombre y apellido; del email solo cuenta la parte local,
+/// porque la misma persona suele registrarse con otro dominio.
+pub fn similarity(a: Identity, b: Identity) -> Similarity {
+    let (nombre_a, nombre_b) = (
+        full_name(a.nombre, a.apellido),
+        full_name(b.nombre, b.apellido),
+    );
+    let nombre = normalized_damerau_levenshtein(&nombre_a, &nombre_b).max(
+        normalized_damerau_levenshtein(&nombre_a, &full_name(b.apellido, b.nombre)),
+    );
+    let email = normalized_damerau_levenshtein(&local_part(a.email), &local_part(b.email));
+    let edad = match (a.edad - b.edad).abs() {
+        0 => 1.0,
+        1 => 0.5,
+        _ => 0.0,
+    };
+
+    Similarity {
+        score: round(PESO_NOMBRE * nombre + PESO_EMAIL * email + PESO_EDAD * edad),
+        nombre: round(nombre),
+        email: round(email),
+        edad,
+    }
+}
+
+/// Alumnos registrados (los de `Identity::candidates_search`) que se parecen a `alumno`
+/// al menos `threshold`, los más parecidos primero. Solo se informa el ID y el nombre:
+/// el resto de los datos de otros alumnos no se expone al que crea uno nuevo.
+pub fn duplicate_candidates(
+    alumno: Identity,
+    registrados: Vec<Alumno>,
+    threshold: f64,
+) -> Vec<DuplicateCandidate> {
+    let mut candidates: Vec<DuplicateCandidate> = registrados
+        .into_iter()
+        .filter_map(|registrado| {
+            let similitud = similarity(alumno, Identity::from(&registrado));
+            (similitud.score >= threshold).then_some(DuplicateCandidate {
+                id: registrado.id,
+                nombre: registrado.nombre,
+                apellido: registrado.apellido,
+                similitud,
+            })
+        })
+        .collect();
+    candidates.sort_by(|a, b| {
+        b.similitud
+            .score
+            .total_cmp(&a.similitud.score)
+            .then(a.id.cmp(&b.id))
+    });
+    candidates
+}
+
+/// Búsqueda de los candidatos de un lote del reporte de duplicados: los alumnos que
+/// comparten alguna clave de `Identity::blocking_keys` con alguno del lote
+pub fn batch_candidates_search(alumnos: &[Alumno]) -> SearchQuery {
+    let keys: BTreeSet<String> = alumnos
+        .iter()
+        .flat_map(|alumno| Identity::from(alumno).blocking_keys())
+        .collect();
+    SearchQuery::names_starting_with(keys.into_iter().collect())
+}
+
+/// Pares que forman los alumnos de un lote con sus candidatos (los de
+/// `batch_candidates_search`) y que se parecen al menos `threshold`. Solo se comparan los
+/// que comparten alguna clave de `Identity::blocking_keys`, y cada alumno únicamente con
+/// los candidatos de ID mayor: al recorrer la tabla por lotes cada par aparece una vez.
+pub fn duplicate_pairs(
+    alumnos: &[Alumno],
+    candidatos: &[Alumno],
+    threshold: f64,
+) -> Vec<DuplicatePair> {
+    let candidatos: Vec<(&Alumno, BTreeSet<String>)> = candidatos
+        .iter()
+        .map(|candidato| {
+            let keys = Identity::from(candidato).blocking_keys();
+            (candidato, keys.into_iter().collect())
+        })
+        .collect();
+
+    let mut pairs = Vec::new();
+    for alumno in alumnos {
+        let keys = Identity::from(alumno).blocking_keys();
+        for (duplicado, claves) in &candidatos {
+            if duplicado.id <= alumno.id || !keys.iter().any(|key| claves.contains(key)) {
+                continue;
+            }
+            let similitud = similarity(alumno.into(), (*duplicado).into());
+            if similitud.score >= threshold {
+                pairs.push(DuplicatePair {
+                    alumno: alumno.clone(),
+                    duplicado: (*duplicado).clone(),
+                    similitud,
+                });
+            }
+        }
+    }
+    pairs
+}
+
+/// Reporte de duplicados que se arma lote a lote: cuenta todos los pares, pero guarda
+/// solo los `limit` más parecidos (y por ID en los empates)
+#[derive(Debug)]
+pub struct DuplicateReport {
+    pairs: Vec<DuplicatePair>,
+    total: i64,
+    limit: usize,
+}
+
+impl DuplicateReport {
+    pub fn new(limit: i64) -> Self {
+        Self {
+            pairs: Vec::new(),
+            total: 0,
+            limit: limit.max(0) as usize,
+        }
+    }
+
+    /// Agrega los pares de un lote (ver `duplicate_pairs`)
+    pub fn extend(&mut self, pairs: Vec<DuplicatePair>) {
+        self.total += pairs.len() as i64;
+        self.pairs.extend(pairs);
+        self.pairs.sort_by(|a, b| {
+            b.similitud
+                .score
+                .total_cmp(&a.similitud.score)
+                .then(a.alumno.id.cmp(&b.alumno.id))
+                .then(a.duplicado.id.cmp(&b.duplicado.id))
+        });
+        self.pairs.truncate(self.limit);
+    }
+
+    /// Pares encontrados, aunque no todos se guarden
+    pub fn total(&self) -> i64 {
+        self.total
+    }
+
+    /// Los `limit` pares más parecidos, primero el más parecido
+    pub fn into_pairs(self) -> Vec<DuplicatePair> {
+        self.pairs
+    }
+}
+
+// "José  Pérez-Díaz" → "jose perez diaz"
+fn full_name(nombre: &str, apellido: &str) -> String {
+    words(&format!("{nombre} {apellido}")).join(" ")
+}
+
+// "Juan.Pérez@email.com" → "juanperez"
+fn local_part(email: &str) -> String {
+    let local = email.split_once('@').map_or(email, |(local, _)| local);
+    fold(local)
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+// Tres decimales bastan para comparar y evitan ruido de punto flotante en el JSON
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
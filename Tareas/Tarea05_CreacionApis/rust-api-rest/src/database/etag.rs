@@ -0,0 +1,136 @@
+use axum::http::{HeaderMap, HeaderName, HeaderValue, header};
+
+use crate::error::AppError;
+
+/// ETag de un alumno: su versión entre comillas, p. ej. `"3"`. Es fuerte porque la
+/// versión cambia con cada modificación del alumno.
+pub fn etag(version: i32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{version}\"")).expect("un número es un ETag válido")
+}
+
+/// Verifica que el alumno siga en la versión esperada (`None` acepta cualquiera);
+/// si otra request lo modificó antes es un 412
+pub fn check_version(current: i32, expected: Option<i32>) -> Result<(), AppError> {
+    match expected {
+        Some(expected) if expected != current => Err(version_mismatch()),
+        _ => Ok(()),
+    }
+}
+
+/// Error de una escritura sobre un alumno que ya no está en la versión esperada
+pub fn version_mismatch() -> AppError {
+    AppError::PreconditionFailed(
+        "El alumno fue modificado por otra request; vuelva a obtenerlo y reintente".to_string(),
+    )
+}
+
+/// Valor de `If-Match` o `If-None-Match`: `*` o una lista de ETags
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityTags {
+    Any,
+    List(Vec<EntityTag>),
+}
+
+/// ETag recibido del cliente; los débiles (`W/"3"`) solo sirven para `If-None-Match`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityTag {
+    pub weak: bool,
+    pub tag: String,
+}
+
+impl EntityTags {
+    /// Lee la cabecera (puede venir repetida); `None` si no se envió
+    pub fn from_headers(headers: &HeaderMap, name: &HeaderName) -> Option<Self> {
+        let mut tags = Vec::new();
+        let mut present = false;
+
+        for value in headers.get_all(name) {
+            present = true;
+            // Un valor que no es texto no coincide con ningún ETag
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+            for item in value.split(',').map(str::trim) {
+                if item == "*" {
+                    return Some(Self::Any);
+                }
+                if item.is_empty() {
+                    continue;
+                }
+                let (weak, tag) = match item.strip_prefix("W/") {
+                    Some(tag) => (true, tag),
+                    None => (false, item),
+                };
+                tags.push(EntityTag {
+                    weak,
+                    tag: tag.trim_matches('"').to_string(),
+                });
+            }
+        }
+
+        present.then_some(Self::List(tags))
+    }
+
+    /// Comparación fuerte de `If-Match`: solo un ETag fuerte igual a la versión
+    pub fn matches_strong(&self, version: i32) -> bool {
+        match self {
+            Self::Any => true,
+            Self::List(tags) => tags
+                .iter()
+                .any(|tag| !tag.weak && tag.tag == version.to_string()),
+        }
+    }
+
+    /// Comparación débil de `If-None-Match`: se ignora el prefijo `W/`
+    pub fn matches_weak(&self, version: i32) -> bool {
+        match self {
+            Self::Any => true,
+            Self::List(tags) => tags.iter().any(|tag| tag.tag == version.to_string()),
+        }
+    }
+}
+
+/// Condición `If-Match` de una escritura. Sin la cabecera es un 428 si `required`; con
+/// ella, el alumno debe estar en una de las versiones enviadas (o existir, con `*`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IfMatch(Option<EntityTags>);
+
+impl IfMatch {
+    pub fn from_headers(headers: &HeaderMap, required: bool) -> Result<Self, AppError> {
+        let tags = EntityTags::from_headers(headers, &header::IF_MATCH);
+        if tags.is_none() && required {
+            return Err(AppError::PreconditionRequired(
+                "Se requiere la cabecera If-Match con el ETag del alumno".to_string(),
+            ));
+        }
+        Ok(Self(tags))
+    }
+
+    /// Indica si hay que leer la versión actual del alumno para evaluar la condición
+    pub fn needs_version(&self) -> bool {
+        matches!(self.0, Some(EntityTags::List(_)))
+    }
+
+    /// Error de una escritura sobre un alumno que no existe: sin la cabecera es un 404; con
+    /// ella (también con `*`) la condición no se cumple y es un 412 (RFC 9110, 13.1.1)
+    pub fn not_found(&self) -> AppError {
+        match self.0 {
+            Some(_) => AppError::PreconditionFailed(
+                "El alumno no existe; la condición If-Match no se cumple".to_string(),
+            ),
+            None => AppError::NotFound("Alumno no encontrado".to_string()),
+        }
+    }
+
+    /// Evalúa la condición con la versión actual; devuelve la versión que debe seguir
+    /// teniendo el alumno al escribir (`None` si no hay condición o es `*`)
+    pub fn expected_version(&self, current: i32) -> Result<Option<i32>, AppError> {
+        match &self.0 {
+            Some(tags @ EntityTags::List(_)) if !tags.matches_strong(current) => {
+                Err(version_mismatch())
+            }
+            Some(EntityTags::List(_)) => Ok(Some(current)),
+            _ => Ok(None),
+        }
+    }
+}
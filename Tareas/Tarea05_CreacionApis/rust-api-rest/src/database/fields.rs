@@ -0,0 +1,58 @@
+use crate::{database::AlumnoColumn, error::AppError, models::PartialAlumno};
+
+/// Campos pedidos con `?fields=id,nombre,apellido`; sin el parámetro se devuelven todos
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldSet {
+    columns: Option<Vec<AlumnoColumn>>,
+}
+
+impl FieldSet {
+    /// Interpreta una lista de columnas separadas por coma; un nombre desconocido es un 400
+    pub fn parse(spec: Option<&str>) -> Result<Self, AppError> {
+        let mut columns: Vec<AlumnoColumn> = Vec::new();
+
+        for item in spec.unwrap_or_default().split(',').map(str::trim) {
+            if item.is_empty() {
+                continue;
+            }
+            let column = AlumnoColumn::parse(item, "fields")?;
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        }
+
+        Ok(Self {
+            columns: (!columns.is_empty()).then_some(columns),
+        })
+    }
+
+    pub fn contains(&self, column: AlumnoColumn) -> bool {
+        self.columns
+            .as_ref()
+            .is_none_or(|columns| columns.contains(&column))
+    }
+
+    /// Columnas del SELECT: las pedidas más `required` (las que el servidor necesita, como
+    /// las del orden para armar los cursores), en el orden de la tabla; `*` si son todas
+    pub fn select_list(&self, required: &[AlumnoColumn]) -> String {
+        if self.columns.is_none() {
+            return "*".to_string();
+        }
+        AlumnoColumn::ALL
+            .into_iter()
+            .filter(|column| self.contains(*column) || required.contains(column))
+            .map(AlumnoColumn::column)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Deja en el alumno solo los campos pedidos
+    pub fn project(&self, mut alumno: PartialAlumno) -> PartialAlumno {
+        for column in AlumnoColumn::ALL {
+            if !self.contains(column) {
+                column.clear(&mut alumno);
+            }
+        }
+        alumno
+    }
+}
@@ -0,0 +1,318 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Database, Encode, QueryBuilder, Type};
+
+use super::{FieldSet, PageStart, SearchQuery, Sort, fold};
+use crate::models::{Alumno, PaginationQuery};
+
+/// Caracter de escape usado en los patrones LIKE
+const LIKE_ESCAPE: char = '!';
+
+/// Filtros para el listado de alumnos, construidos a partir de los query params.
+/// Todos los valores se envían como parámetros bindeados, nunca concatenados al SQL.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AlumnoFilter {
+    /// Texto de la búsqueda de texto completo (ver `SearchQuery`)
+    pub search: Option<String>,
+    /// Carreras aceptadas; vacío no filtra
+    pub carreras: Vec<String>,
+    pub activo: Option<bool>,
+    pub edad: Bounds<i32>,
+    pub semestre: Bounds<i32>,
+    pub promedio: Bounds<f64>,
+    pub fecha_registro: Bounds<DateTime<Utc>>,
+    /// Dominios de email aceptados (lo que va después de `@`); vacío no filtra
+    pub email_dominios: Vec<String>,
+}
+
+/// Rango inclusivo para filtrar una columna; cualquiera de los extremos puede faltar
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Bounds<T> {
+    pub min: Option<T>,
+    pub max: Option<T>,
+}
+
+impl<T: PartialOrd + Copy> Bounds<T> {
+    pub fn new(min: Option<T>, max: Option<T>) -> Self {
+        Self { min, max }
+    }
+
+    /// Evalúa el rango en memoria
+    pub fn contains(&self, value: T) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+
+    /// Agrega `column >= min` y `column <= max`; `bind` envía cada extremo como parámetro
+    fn push<DB: Database>(
+        &self,
+        builder: &mut QueryBuilder<'static, DB>,
+        column: &str,
+        bind: impl Fn(&mut QueryBuilder<'static, DB>, T),
+    ) {
+        if let Some(min) = self.min {
+            builder.push(format!(" AND {column} >= "));
+            bind(builder, min);
+        }
+        if let Some(max) = self.max {
+            builder.push(format!(" AND {column} <= "));
+            bind(builder, max);
+        }
+    }
+}
+
+impl AlumnoFilter {
+    pub fn search(&self) -> Option<SearchQuery> {
+        self.search.as_deref().map(SearchQuery::new)
+    }
+
+    /// Agrega la tabla del FROM: `alumnos`, o al buscar solo los alumnos que coinciden
+    /// con su columna `score`
+    pub fn push_source<DB>(&self, builder: &mut QueryBuilder<'static, DB>)
+    where
+        DB: Database,
+        String: Encode<'static, DB> + Type<DB>,
+    {
+        match self.search() {
+            Some(search) => search.push_source(builder),
+            None => {
+                builder.push("alumnos");
+            }
+        }
+    }
+
+    /// Agrega la cláusula WHERE con todas las condiciones del filtro.
+    /// Es genérico sobre el motor: `QueryBuilder` genera `?` o `$n` según corresponda.
+    pub fn push_where<DB>(&self, builder: &mut QueryBuilder<'static, DB>)
+    where
+        DB: Database,
+        String: Encode<'static, DB> + Type<DB>,
+        bool: Encode<'static, DB> + Type<DB>,
+        i32: Encode<'static, DB> + Type<DB>,
+        f64: Encode<'static, DB> + Type<DB>,
+        DateTime<Utc>: Encode<'static, DB> + Type<DB>,
+    {
+        builder.push(" WHERE 1=1");
+
+        // PostgreSQL distingue mayúsculas; MySQL y SQLite no, por la collation de las columnas
+        let case_sensitive = DB::NAME == "PostgreSQL";
+
+        match self.carreras.as_slice() {
+            [] => {}
+            [carrera] if case_sensitive => {
+                builder
+                    .push(" AND LOWER(carrera) = LOWER(")
+                    .push_bind(carrera.clone())
+                    .push(")");
+            }
+            [carrera] => {
+                builder.push(" AND carrera = ").push_bind(carrera.clone());
+            }
+            carreras => {
+                builder.push(if case_sensitive {
+                    " AND LOWER(carrera) IN ("
+                } else {
+                    " AND carrera IN ("
+                });
+                for (i, carrera) in carreras.iter().enumerate() {
+                    if i > 0 {
+                        builder.push(", ");
+                    }
+                    if case_sensitive {
+                        builder.push("LOWER(").push_bind(carrera.clone()).push(")");
+                    } else {
+                        builder.push_bind(carrera.clone());
+                    }
+                }
+                builder.push(")");
+            }
+        }
+
+        if let Some(activo) = self.activo {
+            builder.push(" AND activo = ").push_bind(activo);
+        }
+
+        self.edad.push(builder, "edad", |b, v| {
+            b.push_bind(v);
+        });
+        self.semestre.push(builder, "semestre", |b, v| {
+            b.push_bind(v);
+        });
+        self.promedio.push(builder, "promedio", |b, v| {
+            b.push_bind(v);
+        });
+        self.fecha_registro
+            .push(builder, "fecha_registro", push_fecha);
+
+        // El email termina en `@dominio`
+        if !self.email_dominios.is_empty() {
+            let like = if case_sensitive { "ILIKE" } else { "LIKE" };
+            builder.push(" AND (");
+            for (i, dominio) in self.email_dominios.iter().enumerate() {
+                if i > 0 {
+                    builder.push(" OR ");
+                }
+                builder
+                    .push(format!("email {like} "))
+                    .push_bind(format!("%@{}", escape_like(dominio)))
+                    .push(format!(" ESCAPE '{LIKE_ESCAPE}'"));
+            }
+            builder.push(")");
+        }
+    }
+
+    /// Evalúa el filtro en memoria con la misma semántica que el SQL generado.
+    /// Las comparaciones de texto ignoran mayúsculas y acentos, igual que la collation de
+    /// MySQL (`Ingenieria` coincide con `Ingeniería`).
+    pub fn matches(&self, alumno: &Alumno) -> bool {
+        if self.search.is_some() && self.score(alumno).is_none() {
+            return false;
+        }
+
+        if !self.carreras.is_empty()
+            && !self
+                .carreras
+                .iter()
+                .any(|carrera| fold(&alumno.carrera) == fold(carrera))
+        {
+            return false;
+        }
+
+        if let Some(activo) = self.activo
+            && alumno.activo != activo
+        {
+            return false;
+        }
+
+        if !self.email_dominios.is_empty() {
+            let email = fold(&alumno.email);
+            if !self
+                .email_dominios
+                .iter()
+                .any(|dominio| email.ends_with(&format!("@{}", fold(dominio))))
+            {
+                return false;
+            }
+        }
+
+        self.edad.contains(alumno.edad)
+            && self.semestre.contains(alumno.semestre)
+            && self.promedio.contains(alumno.promedio)
+            && self.fecha_registro.contains(alumno.fecha_registro)
+    }
+
+    /// Relevancia del alumno para la búsqueda en memoria; `None` sin búsqueda o si no coincide
+    pub fn score(&self, alumno: &Alumno) -> Option<f64> {
+        self.search()?.score(alumno)
+    }
+
+    /// Query para obtener una página de alumnos en el orden solicitado (con el ID como
+    /// desempate), a partir de una posición o de un cursor. Solo lee las columnas de
+    /// `fields` y las del orden, que hacen falta para los cursores; al buscar, también `score`.
+    pub fn select_query<DB>(
+        &self,
+        fields: &FieldSet,
+        sort: &Sort,
+        start: &PageStart,
+        limit: i64,
+    ) -> QueryBuilder<'static, DB>
+    where
+        DB: Database,
+        String: Encode<'static, DB> + Type<DB>,
+        bool: Encode<'static, DB> + Type<DB>,
+        i32: Encode<'static, DB> + Type<DB>,
+        i64: Encode<'static, DB> + Type<DB>,
+        f64: Encode<'static, DB> + Type<DB>,
+        DateTime<Utc>: Encode<'static, DB> + Type<DB>,
+    {
+        let mut columns = fields.select_list(&sort.columns());
+        if self.search.is_some() && columns != "*" {
+            columns.push_str(", score");
+        }
+        let mut builder = QueryBuilder::new(match start {
+            PageStart::Before(_) => format!("SELECT * FROM (SELECT {columns} FROM "),
+            _ => format!("SELECT {columns} FROM "),
+        });
+        self.push_source(&mut builder);
+        self.push_where(&mut builder);
+        start.push_window(&mut builder, sort, limit);
+        builder
+    }
+
+    /// Query para contar el total de alumnos que cumplen el filtro
+    pub fn count_query<DB>(&self) -> QueryBuilder<'static, DB>
+    where
+        DB: Database,
+        String: Encode<'static, DB> + Type<DB>,
+        bool: Encode<'static, DB> + Type<DB>,
+        i32: Encode<'static, DB> + Type<DB>,
+        f64: Encode<'static, DB> + Type<DB>,
+        DateTime<Utc>: Encode<'static, DB> + Type<DB>,
+    {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) AS total FROM ");
+        self.push_source(&mut builder);
+        self.push_where(&mut builder);
+        builder
+    }
+}
+
+impl From<&PaginationQuery> for AlumnoFilter {
+    fn from(params: &PaginationQuery) -> Self {
+        Self {
+            search: non_empty(params.search.as_deref()),
+            carreras: list(params.carrera.as_deref()),
+            activo: params.activo,
+            edad: Bounds::new(params.edad_min, params.edad_max),
+            semestre: Bounds::new(params.semestre_min, params.semestre_max),
+            promedio: Bounds::new(params.promedio_min, params.promedio_max),
+            fecha_registro: Bounds::new(params.registrado_desde, params.registrado_hasta),
+            email_dominios: list(params.email_dominio.as_deref())
+                .into_iter()
+                .map(|dominio| dominio.trim_start_matches('@').to_string())
+                .filter(|dominio| !dominio.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Envía una fecha como parámetro en el formato con el que el motor la compara.
+/// SQLite guarda CURRENT_TIMESTAMP como texto 'YYYY-MM-DD HH:MM:SS' y compara como texto.
+pub(crate) fn push_fecha<DB>(builder: &mut QueryBuilder<'static, DB>, value: DateTime<Utc>)
+where
+    DB: Database,
+    String: Encode<'static, DB> + Type<DB>,
+    DateTime<Utc>: Encode<'static, DB> + Type<DB>,
+{
+    if DB::NAME == "SQLite" {
+        builder.push_bind(value.format("%Y-%m-%d %H:%M:%S").to_string());
+    } else {
+        builder.push_bind(value);
+    }
+}
+
+/// Escapa los comodines de LIKE para que el texto se busque de forma literal
+pub fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '%' || c == '_' || c == LIKE_ESCAPE {
+            escaped.push(LIKE_ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Ignora filtros vacíos o compuestos solo por espacios
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+// Valores separados por coma, ignorando los vacíos
+fn list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|v| non_empty(Some(v)))
+        .collect()
+}
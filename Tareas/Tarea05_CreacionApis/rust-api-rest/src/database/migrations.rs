@@ -0,0 +1,16 @@
+use sqlx::migrate::Migrator;
+
+/// Migraciones de MySQL embebidas en el binario (`migrations/mysql`)
+pub static MYSQL_MIGRATOR: Migrator = sqlx::migrate!("./migrations/mysql");
+
+/// Migraciones de SQLite embebidas en el binario (`migrations/sqlite`)
+#[cfg(feature = "sqlite")]
+pub static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations/sqlite");
+
+/// Migraciones de PostgreSQL embebidas en el binario (`migrations/postgres`)
+#[cfg(feature = "postgres")]
+pub static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+
+/// Versión de la última migración aplicada con éxito (misma tabla en los tres motores)
+pub const SCHEMA_VERSION_QUERY: &str =
+    "SELECT MAX(version) FROM _sqlx_migrations WHERE success = TRUE";
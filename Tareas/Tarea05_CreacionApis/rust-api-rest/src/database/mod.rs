@@ -1,2 +1,26 @@
+pub mod changes;
+pub mod columns;
 pub mod connection;
-pub use connection::*;
\ No newline at end of file
+pub mod duplicates;
+pub mod etag;
+pub mod fields;
+pub mod filters;
+pub mod migrations;
+pub mod pagination;
+pub mod patch;
+pub mod retry;
+pub mod search;
+pub mod sort;
+pub use changes::*;
+pub use columns::*;
+pub use connection::*;
+pub use duplicates::*;
+pub use etag::*;
+pub use fields::*;
+pub use filters::*;
+pub use migrations::*;
+pub use pagination::*;
+pub use patch::*;
+pub use retry::*;
+pub use search::*;
+pub use sort::*;
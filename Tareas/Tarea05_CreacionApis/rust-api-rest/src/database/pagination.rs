@@ -0,0 +1,191 @@
+use std::cmp::Ordering;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Database, Encode, QueryBuilder, Type};
+
+use crate::{
+    database::{Sort, SortValue},
+    error::AppError,
+    models::{PaginationQuery, PartialAlumno},
+};
+
+/// Posición de un alumno en el listado: los valores de las columnas de `sort` y el `id`
+/// que desempata. Se envía al cliente como un texto opaco (JSON en base64 URL-safe).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Orden canónico con el que se generó el cursor
+    pub sort: String,
+    pub values: Vec<SortValue>,
+    pub id: i32,
+}
+
+impl Cursor {
+    pub fn of(alumno: &PartialAlumno, sort: &Sort) -> Result<Self, AppError> {
+        let (values, id) = sort.position(alumno)?;
+        Ok(Self {
+            sort: sort.to_string(),
+            values,
+            id,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("el cursor siempre se serializa");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    /// Decodifica un cursor recibido; cualquier valor alterado, o un cursor generado con
+    /// otro orden, es un 400
+    pub fn decode(value: &str, sort: &Sort) -> Result<Self, AppError> {
+        let cursor: Self = URL_SAFE_NO_PAD
+            .decode(value)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| AppError::BadRequest("Cursor inválido".to_string()))?;
+
+        if cursor.sort != sort.to_string() || !sort.accepts(&cursor.values) {
+            return Err(AppError::BadRequest(
+                "El cursor no corresponde al orden solicitado".to_string(),
+            ));
+        }
+        Ok(cursor)
+    }
+
+    fn position(&self, alumno: &PartialAlumno, sort: &Sort) -> Ordering {
+        let values: Vec<Option<SortValue>> = self.values.iter().cloned().map(Some).collect();
+        sort.compare((&sort.values(alumno), alumno.id), (&values, Some(self.id)))
+    }
+
+    /// Indica si el alumno aparece después del cursor en el listado
+    pub fn precedes(&self, alumno: &PartialAlumno, sort: &Sort) -> bool {
+        self.position(alumno, sort) == Ordering::Greater
+    }
+
+    /// Indica si el alumno aparece antes del cursor en el listado
+    pub fn follows(&self, alumno: &PartialAlumno, sort: &Sort) -> bool {
+        self.position(alumno, sort) == Ordering::Less
+    }
+}
+
+/// Desde dónde se lee una página del listado
+#[derive(Debug, Clone, PartialEq)]
+pub enum PageStart {
+    /// Modo por páginas (`?page=`): salta las primeras filas
+    Offset(i64),
+    /// Filas posteriores al cursor (`?after=`)
+    After(Cursor),
+    /// Filas anteriores al cursor (`?before=`)
+    Before(Cursor),
+}
+
+impl PageStart {
+    /// Interpreta `page`, `after` y `before`; el modo por cursor no se combina con `page`
+    pub fn from_query(params: &PaginationQuery, sort: &Sort, limit: i64) -> Result<Self, AppError> {
+        let combined = |message: &str| Err(AppError::BadRequest(message.to_string()));
+
+        match (params.after.as_deref(), params.before.as_deref()) {
+            (Some(_), Some(_)) => combined("after y before no se pueden combinar"),
+            (Some(_), None) | (None, Some(_)) if params.page.is_some() => {
+                combined("page no se puede combinar con after o before")
+            }
+            (Some(after), None) => Ok(Self::After(Cursor::decode(after, sort)?)),
+            (None, Some(before)) => Ok(Self::Before(Cursor::decode(before, sort)?)),
+            (None, None) => {
+                let page = i64::from(params.page.unwrap_or(1).max(1));
+                Ok(Self::Offset((page - 1) * limit))
+            }
+        }
+    }
+
+    /// Agrega a la consulta la posición, el orden y el límite de la página
+    pub fn push_window<DB>(&self, builder: &mut QueryBuilder<'static, DB>, sort: &Sort, limit: i64)
+    where
+        DB: Database,
+        String: Encode<'static, DB> + Type<DB>,
+        bool: Encode<'static, DB> + Type<DB>,
+        i32: Encode<'static, DB> + Type<DB>,
+        i64: Encode<'static, DB> + Type<DB>,
+        f64: Encode<'static, DB> + Type<DB>,
+        DateTime<Utc>: Encode<'static, DB> + Type<DB>,
+    {
+        match self {
+            Self::Offset(offset) => {
+                sort.push_order_by(builder, false);
+                builder
+                    .push(" LIMIT ")
+                    .push_bind(limit)
+                    .push(" OFFSET ")
+                    .push_bind(*offset);
+            }
+            Self::After(cursor) => {
+                sort.push_keyset(builder, &cursor.values, cursor.id, false);
+                sort.push_order_by(builder, false);
+                builder.push(" LIMIT ").push_bind(limit);
+            }
+            // Las filas más cercanas al cursor se leen en orden inverso y la consulta
+            // externa (abierta en `AlumnoFilter::select_query`) las devuelve al orden del listado
+            Self::Before(cursor) => {
+                sort.push_keyset(builder, &cursor.values, cursor.id, true);
+                sort.push_order_by(builder, true);
+                builder.push(" LIMIT ").push_bind(limit).push(") AS pagina");
+                sort.push_order_by(builder, false);
+            }
+        }
+    }
+
+    /// Arma la página a partir de hasta `limit + 1` filas en el orden del listado;
+    /// la fila extra solo indica que hay más en la dirección de lectura.
+    pub fn page(
+        &self,
+        mut alumnos: Vec<PartialAlumno>,
+        sort: &Sort,
+        limit: i64,
+    ) -> Result<Page, AppError> {
+        let has_more = alumnos.len() as i64 > limit;
+        if has_more {
+            match self {
+                Self::Before(_) => {
+                    alumnos.remove(0);
+                }
+                _ => {
+                    alumnos.pop();
+                }
+            }
+        }
+
+        let (has_prev, has_next) = match self {
+            Self::Offset(offset) => (*offset > 0, has_more),
+            Self::After(_) => (true, has_more),
+            Self::Before(_) => (has_more, true),
+        };
+
+        let cursor = |alumno: &PartialAlumno| Cursor::of(alumno, sort).map(|c| c.encode());
+        Ok(Page {
+            has_prev,
+            has_next,
+            prev_cursor: alumnos
+                .first()
+                .filter(|_| has_prev)
+                .map(cursor)
+                .transpose()?,
+            next_cursor: alumnos
+                .last()
+                .filter(|_| has_next)
+                .map(cursor)
+                .transpose()?,
+            alumnos,
+        })
+    }
+}
+
+/// Página del listado con los cursores para moverse a la anterior y a la siguiente
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub alumnos: Vec<PartialAlumno>,
+    pub has_next: bool,
+    pub has_prev: bool,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
@@ -0,0 +1,115 @@
+use json_patch::{Patch, PatchErrorKind};
+use serde_json::{Map, Value};
+
+use crate::{
+    error::AppError,
+    models::{Alumno, UpdateAlumnoRequest},
+};
+
+/// `Content-Type` de JSON Merge Patch (RFC 7396)
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+
+/// `Content-Type` de JSON Patch (RFC 6902)
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+/// Campos del alumno que el cliente puede modificar; el resto es de solo lectura
+const EDITABLE_FIELDS: [&str; 8] = [
+    "nombre", "apellido", "email", "edad", "carrera", "semestre", "promedio", "activo",
+];
+
+/// Cuerpo de `PATCH /api/alumnos/{id}`, según su `Content-Type`
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlumnoPatch {
+    /// JSON Merge Patch: los campos presentes reemplazan al valor actual y `null` lo borra
+    Merge(Value),
+    /// JSON Patch: operaciones (`add`, `remove`, `replace`, `move`, `copy`, `test`) que se
+    /// aplican en orden; si una falla no se aplica ninguna
+    Json(Patch),
+}
+
+impl AlumnoPatch {
+    /// Interpreta el cuerpo según el `Content-Type`; `application/json` se trata como merge
+    /// patch. Cualquier otro tipo es un 415.
+    pub fn parse(content_type: Option<&str>, body: &[u8]) -> Result<Self, AppError> {
+        let media_type = content_type
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase());
+        let invalid =
+            |error: serde_json::Error| AppError::BadRequest(format!("Patch inválido: {error}"));
+
+        match media_type.as_deref() {
+            Some(MERGE_PATCH_CONTENT_TYPE | "application/json") => serde_json::from_slice(body)
+                .map(Self::Merge)
+                .map_err(invalid),
+            Some(JSON_PATCH_CONTENT_TYPE) => serde_json::from_slice(body)
+                .map(Self::Json)
+                .map_err(invalid),
+            _ => Err(AppError::UnsupportedMediaType(format!(
+                "Content-Type no soportado; use {MERGE_PATCH_CONTENT_TYPE} o {JSON_PATCH_CONTENT_TYPE}"
+            ))),
+        }
+    }
+
+    /// Aplica el patch a la representación JSON del alumno y devuelve solo los campos que
+    /// cambian. Borrar un campo (`null` en merge patch, `remove` en JSON Patch), agregar uno
+    /// desconocido o modificar uno de solo lectura es un 400; un `test` que no se cumple es
+    /// un 409. Las reglas de `UpdateAlumnoRequest` se validan aparte.
+    pub fn changes(&self, alumno: &Alumno) -> Result<UpdateAlumnoRequest, AppError> {
+        let original = serde_json::to_value(alumno).expect("el alumno siempre se serializa");
+        let mut patched = original.clone();
+
+        match self {
+            Self::Merge(patch) => json_patch::merge(&mut patched, patch),
+            Self::Json(patch) => {
+                json_patch::patch(&mut patched, patch).map_err(|error| match error.kind {
+                    PatchErrorKind::TestFailed => AppError::Conflict(format!(
+                        "La operación test sobre '{}' no se cumplió",
+                        error.path
+                    )),
+                    _ => AppError::BadRequest(format!(
+                        "La operación {} del patch no se pudo aplicar sobre '{}'",
+                        error.operation, error.path
+                    )),
+                })?
+            }
+        }
+
+        diff(&original, patched)
+    }
+}
+
+// Campos editables que difieren entre el alumno original y el resultado del patch
+fn diff(original: &Value, patched: Value) -> Result<UpdateAlumnoRequest, AppError> {
+    let (Value::Object(original), Value::Object(patched)) = (original, patched) else {
+        return Err(AppError::BadRequest(
+            "El patch debe producir un objeto alumno".to_string(),
+        ));
+    };
+
+    if let Some(field) = patched.keys().find(|field| !original.contains_key(*field)) {
+        return Err(AppError::BadRequest(format!("Campo desconocido: {field}")));
+    }
+
+    let mut changes = Map::new();
+    for (field, before) in original {
+        match patched.get(field) {
+            None => {
+                return Err(AppError::BadRequest(format!(
+                    "El campo {field} es obligatorio: no admite null ni se puede quitar"
+                )));
+            }
+            Some(after) if after == before => {}
+            Some(after) if EDITABLE_FIELDS.contains(&field.as_str()) => {
+                changes.insert(field.clone(), after.clone());
+            }
+            Some(_) => {
+                return Err(AppError::BadRequest(format!(
+                    "El campo {field} es de solo lectura"
+                )));
+            }
+        }
+    }
+
+    serde_json::from_value(Value::Object(changes))
+        .map_err(|error| AppError::BadRequest(format!("Valor inválido en el patch: {error}")))
+}
@@ -0,0 +1,56 @@
+use std::{fmt::Display, future::Future, time::Duration};
+
+use tokio::time::{Instant, sleep};
+
+/// Política de reintentos con espera exponencial y un plazo total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Tiempo total reintentando; con `Duration::ZERO` se hace un solo intento
+    pub deadline: Duration,
+    /// Espera antes del segundo intento; se duplica en cada fallo
+    pub initial_backoff: Duration,
+    /// Tope de la espera entre intentos
+    pub max_backoff: Duration,
+}
+
+/// Ejecuta `operation` hasta que tenga éxito o se cumpla el plazo de `policy`.
+/// Retorna el último error si el plazo se agota.
+pub async fn retry_with_backoff<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    what: &str,
+    mut operation: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    let deadline = Instant::now() + policy.deadline;
+    let mut backoff = policy.initial_backoff;
+    let mut attempt: u32 = 1;
+
+    loop {
+        let error = match operation().await {
+            Ok(value) => return Ok(value),
+            Err(error) => error,
+        };
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(error);
+        }
+
+        // La última espera se recorta para intentar justo al cumplirse el plazo
+        let delay = backoff.min(remaining);
+        tracing::warn!(
+            attempt,
+            error = %error,
+            retry_in_ms = delay.as_millis() as u64,
+            "⏳ {what} falló, reintentando"
+        );
+
+        sleep(delay).await;
+        backoff = (backoff * 2).min(policy.max_backoff);
+        attempt += 1;
+    }
+}
@@ -0,0 +1,192 @@
+use sqlx::{Database, Encode, QueryBuilder, Type};
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+use crate::models::Alumno;
+
+/// Columnas indexadas para la búsqueda de texto completo
+const SEARCH_COLUMNS: &str = "nombre, apellido, email, carrera";
+
+/// Columnas de la búsqueda por nombre (`SearchQuery::names_starting_with`)
+const NAME_COLUMNS: &str = "nombre, apellido";
+
+/// Búsqueda de texto completo sobre nombre, apellido, email y carrera (`?search=`).
+/// El texto se parte en palabras sin acentos ni mayúsculas y cada una se busca como
+/// prefijo: `andres gom` encuentra a "Andrés Gómez". Deben aparecer todas las palabras.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    terms: Vec<String>,
+    /// Solo nombre y apellido, y basta con que aparezca una de las palabras (ver
+    /// `names_starting_with`)
+    names: bool,
+}
+
+impl SearchQuery {
+    pub fn new(text: &str) -> Self {
+        Self {
+            terms: words(text),
+            names: false,
+        }
+    }
+
+    /// Búsqueda de los alumnos con al menos una palabra del nombre o el apellido que
+    /// empieza con alguno de los prefijos (ya normalizados); la usa la detección de
+    /// duplicados. El email y la carrera no cuentan: `ing` no trae a todos los de
+    /// Ingeniería.
+    pub fn names_starting_with(prefixes: Vec<String>) -> Self {
+        Self {
+            terms: prefixes,
+            names: true,
+        }
+    }
+
+    /// Palabras buscadas, normalizadas; los signos no cuentan (solo separan palabras)
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Consulta en modo booleano de MySQL: `+andres* +gom*` (`andres* gom*` por nombre)
+    pub fn boolean_mode(&self) -> String {
+        let required = if self.names { "" } else { "+" };
+        self.join(|term| format!("{required}{term}*"), " ")
+    }
+
+    /// `tsquery` de PostgreSQL: `andres:* & gom:*` (`andres:* | gom:*` por nombre)
+    pub fn tsquery(&self) -> String {
+        self.join(
+            |term| format!("{term}:*"),
+            if self.names { " | " } else { " & " },
+        )
+    }
+
+    /// Consulta FTS5 de SQLite: `"andres"* "gom"*` (por nombre,
+    /// `{nombre apellido} : ("andres"* OR "gom"*)`, limitada a esas columnas)
+    pub fn fts5(&self) -> String {
+        if self.names {
+            let terms = self.join(|term| format!("\"{term}\"*"), " OR ");
+            return format!("{{nombre apellido}} : ({terms})");
+        }
+        self.join(|term| format!("\"{term}\"*"), " ")
+    }
+
+    fn join(&self, format: impl Fn(&str) -> String, separator: &str) -> String {
+        self.terms
+            .iter()
+            .map(|term| format(term))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    /// Agrega la tabla de la que lee el listado: los alumnos que coinciden, con su
+    /// relevancia en la columna `score` (mayor es más relevante). Cada motor usa su
+    /// índice de texto completo (ver las migraciones `0002_busqueda_alumnos.sql`, y
+    /// `busqueda_nombre.sql` para la búsqueda por nombre).
+    pub fn push_source<DB>(&self, builder: &mut QueryBuilder<'static, DB>)
+    where
+        DB: Database,
+        String: Encode<'static, DB> + Type<DB>,
+    {
+        // Sin palabras (p. ej. solo signos) no hay coincidencias
+        if self.terms.is_empty() {
+            builder.push("(SELECT alumnos.*, 0 AS score FROM alumnos WHERE 1 = 0) AS busqueda");
+            return;
+        }
+
+        match DB::NAME {
+            // Índice GIN sobre alumnos_documento(...), que quita acentos con unaccent
+            "PostgreSQL" => {
+                let document = if self.names {
+                    format!("alumnos_nombre({NAME_COLUMNS})")
+                } else {
+                    format!("alumnos_documento({SEARCH_COLUMNS})")
+                };
+                builder
+                    .push(format!(
+                        "(SELECT alumnos.*, ts_rank({document}, to_tsquery('simple', "
+                    ))
+                    .push_bind(self.tsquery())
+                    .push(format!(
+                        "))::DOUBLE PRECISION AS score FROM alumnos \
+                         WHERE {document} @@ to_tsquery('simple', "
+                    ))
+                    .push_bind(self.tsquery())
+                    .push(")) AS busqueda");
+            }
+            // Tabla FTS5 sincronizada por triggers; bm25 es menor cuanto más relevante
+            "SQLite" => {
+                builder
+                    .push(
+                        "(SELECT alumnos.*, -bm25(alumnos_busqueda) AS score FROM alumnos \
+                         JOIN alumnos_busqueda ON alumnos_busqueda.rowid = alumnos.id \
+                         WHERE alumnos_busqueda MATCH ",
+                    )
+                    .push_bind(self.fts5())
+                    .push(") AS busqueda");
+            }
+            // Índice FULLTEXT; utf8mb4_unicode_ci ya ignora acentos y mayúsculas
+            _ => {
+                let columns = if self.names {
+                    NAME_COLUMNS
+                } else {
+                    SEARCH_COLUMNS
+                };
+                let matches = format!("MATCH({columns}) AGAINST (");
+                builder
+                    .push(format!("(SELECT alumnos.*, {matches}"))
+                    .push_bind(self.boolean_mode())
+                    .push(format!(
+                        " IN BOOLEAN MODE) AS score FROM alumnos WHERE {matches}"
+                    ))
+                    .push_bind(self.boolean_mode())
+                    .push(" IN BOOLEAN MODE)) AS busqueda");
+            }
+        }
+    }
+
+    /// Relevancia en memoria: cuántas palabras del alumno empiezan con cada término.
+    /// `None` si algún término no aparece (por nombre, si no aparece ninguno). La escala
+    /// no es comparable con la de los motores.
+    pub fn score(&self, alumno: &Alumno) -> Option<f64> {
+        if self.terms.is_empty() {
+            return None;
+        }
+        let document = if self.names {
+            words(&format!("{} {}", alumno.nombre, alumno.apellido))
+        } else {
+            words(&format!(
+                "{} {} {} {}",
+                alumno.nombre, alumno.apellido, alumno.email, alumno.carrera
+            ))
+        };
+
+        let mut score = 0;
+        for term in &self.terms {
+            let hits = document
+                .iter()
+                .filter(|word| word.starts_with(term.as_str()))
+                .count();
+            if hits == 0 && !self.names {
+                return None;
+            }
+            score += hits;
+        }
+        (score > 0).then_some(score as f64)
+    }
+}
+
+/// Minúsculas y sin acentos (`Andrés` → `andres`), igual que la collation de MySQL,
+/// `unaccent` en PostgreSQL y `remove_diacritics` en FTS5
+pub fn fold(text: &str) -> String {
+    text.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+// Palabras normalizadas del texto; los signos separan palabras (también en los emails)
+pub(crate) fn words(text: &str) -> Vec<String> {
+    fold(text)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
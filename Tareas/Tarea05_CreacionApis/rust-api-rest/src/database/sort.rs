@@ -0,0 +1,317 @@
+use std::{cmp::Ordering, fmt};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Database, Encode, QueryBuilder, Type, error::UnexpectedNullError};
+
+use super::{AlumnoColumn, fold, push_fecha};
+use crate::{error::AppError, models::PartialAlumno};
+
+/// Valor de una columna de orden, tal como viaja en los cursores
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortValue {
+    Int(i32),
+    Text(String),
+    Float(f64),
+    Bool(bool),
+    Fecha(DateTime<Utc>),
+}
+
+impl SortValue {
+    /// Misma semántica que el ORDER BY de MySQL: el texto se compara sin mayúsculas ni
+    /// acentos (`Álvarez` antes que `Benítez`)
+    fn compare(&self, other: &SortValue) -> Ordering {
+        match (self, other) {
+            (SortValue::Int(a), SortValue::Int(b)) => a.cmp(b),
+            (SortValue::Text(a), SortValue::Text(b)) => fold(a).cmp(&fold(b)),
+            (SortValue::Float(a), SortValue::Float(b)) => a.total_cmp(b),
+            (SortValue::Bool(a), SortValue::Bool(b)) => a.cmp(b),
+            (SortValue::Fecha(a), SortValue::Fecha(b)) => a.cmp(b),
+            _ => Ordering::Equal,
+        }
+    }
+
+    fn push_bind<DB>(&self, builder: &mut QueryBuilder<'static, DB>)
+    where
+        DB: Database,
+        String: Encode<'static, DB> + Type<DB>,
+        bool: Encode<'static, DB> + Type<DB>,
+        i32: Encode<'static, DB> + Type<DB>,
+        f64: Encode<'static, DB> + Type<DB>,
+        DateTime<Utc>: Encode<'static, DB> + Type<DB>,
+    {
+        match self {
+            SortValue::Int(value) => builder.push_bind(*value),
+            SortValue::Text(value) => builder.push_bind(value.clone()),
+            SortValue::Float(value) => builder.push_bind(*value),
+            SortValue::Bool(value) => builder.push_bind(*value),
+            SortValue::Fecha(value) => {
+                push_fecha(builder, *value);
+                builder
+            }
+        };
+    }
+}
+
+/// Columna de orden con su dirección
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: AlumnoColumn,
+    pub descending: bool,
+}
+
+/// Orden del listado (`?sort=apellido,-promedio`). Los empates siempre se resuelven por
+/// `id` en la dirección de la última columna, para que el orden sea total y estable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sort {
+    keys: Vec<SortKey>,
+}
+
+impl Default for Sort {
+    /// Más recientes primero
+    fn default() -> Self {
+        Self {
+            keys: vec![SortKey {
+                field: AlumnoColumn::FechaRegistro,
+                descending: true,
+            }],
+        }
+    }
+}
+
+impl Sort {
+    /// Más relevantes primero; es el orden por defecto de una búsqueda
+    pub fn relevance() -> Self {
+        Self {
+            keys: vec![SortKey {
+                field: AlumnoColumn::Score,
+                descending: true,
+            }],
+        }
+    }
+
+    /// Interpreta una lista de columnas separadas por coma; `-` al inicio invierte el orden.
+    /// Sin columnas se usa el orden por defecto.
+    pub fn parse(spec: Option<&str>) -> Result<Self, AppError> {
+        Self::from_query(spec, false)
+    }
+
+    /// Como `parse`, pero al buscar (`searching`) acepta `score` y ordena por relevancia
+    /// si no se pidió otro orden
+    pub fn from_query(spec: Option<&str>, searching: bool) -> Result<Self, AppError> {
+        let mut keys: Vec<SortKey> = Vec::new();
+
+        for item in spec.unwrap_or_default().split(',').map(str::trim) {
+            if item.is_empty() {
+                continue;
+            }
+            let (descending, column) = match item.strip_prefix('-') {
+                Some(column) => (true, column),
+                None => (false, item.strip_prefix('+').unwrap_or(item)),
+            };
+            let field = if column == AlumnoColumn::Score.column() {
+                if !searching {
+                    return Err(AppError::BadRequest(
+                        "El orden por score solo se puede usar con search".to_string(),
+                    ));
+                }
+                AlumnoColumn::Score
+            } else {
+                AlumnoColumn::parse(column, "sort")?
+            };
+            if keys.iter().any(|key| key.field == field) {
+                return Err(AppError::BadRequest(format!(
+                    "El campo '{column}' aparece más de una vez en sort"
+                )));
+            }
+            keys.push(SortKey { field, descending });
+        }
+
+        if keys.is_empty() {
+            Ok(if searching {
+                Self::relevance()
+            } else {
+                Self::default()
+            })
+        } else {
+            Ok(Self { keys })
+        }
+    }
+
+    pub fn keys(&self) -> &[SortKey] {
+        &self.keys
+    }
+
+    /// Columnas del orden incluyendo el desempate por `id`
+    fn total_keys(&self) -> Vec<SortKey> {
+        let mut keys = self.keys.clone();
+        if !keys.iter().any(|key| key.field == AlumnoColumn::Id) {
+            keys.push(SortKey {
+                field: AlumnoColumn::Id,
+                descending: keys.last().is_some_and(|key| key.descending),
+            });
+        }
+        keys
+    }
+
+    /// Columnas que hay que leer para ubicar un alumno en el orden (incluye el `id`)
+    pub fn columns(&self) -> Vec<AlumnoColumn> {
+        self.total_keys().iter().map(|key| key.field).collect()
+    }
+
+    /// Valores de las columnas del orden (sin el desempate); `None` si la columna es NULL
+    pub fn values(&self, alumno: &PartialAlumno) -> Vec<Option<SortValue>> {
+        self.keys
+            .iter()
+            .map(|key| key.field.value(alumno))
+            .collect()
+    }
+
+    /// Posición de un alumno para un cursor: valores de las columnas del orden e `id`. El
+    /// listado siempre selecciona esas columnas, aunque no se pidan en `?fields=`; un
+    /// cursor no puede guardar NULL, así que una columna NULL es un error de la base.
+    pub fn position(&self, alumno: &PartialAlumno) -> Result<(Vec<SortValue>, i32), AppError> {
+        let null = |column: AlumnoColumn| {
+            AppError::Database(sqlx::Error::ColumnDecode {
+                index: column.column().to_string(),
+                source: Box::new(UnexpectedNullError),
+            })
+        };
+        let values = self
+            .keys
+            .iter()
+            .map(|key| key.field.value(alumno).ok_or_else(|| null(key.field)))
+            .collect::<Result<_, _>>()?;
+        let id = alumno.id.ok_or_else(|| null(AlumnoColumn::Id))?;
+        Ok((values, id))
+    }
+
+    /// Verifica que los valores de un cursor correspondan a las columnas del orden
+    pub fn accepts(&self, values: &[SortValue]) -> bool {
+        values.len() == self.keys.len()
+            && self
+                .keys
+                .iter()
+                .zip(values)
+                .all(|(key, value)| key.field.accepts(value))
+    }
+
+    /// Compara dos posiciones `(valores, id)` según el orden del listado; como en MySQL,
+    /// NULL va antes que cualquier valor en orden ascendente
+    pub fn compare(
+        &self,
+        a: (&[Option<SortValue>], Option<i32>),
+        b: (&[Option<SortValue>], Option<i32>),
+    ) -> Ordering {
+        for (key, (va, vb)) in self.keys.iter().zip(a.0.iter().zip(b.0)) {
+            let ordering = match (va, vb) {
+                (Some(va), Some(vb)) => va.compare(vb),
+                _ => va.is_some().cmp(&vb.is_some()),
+            };
+            let ordering = if key.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        let tiebreak = self
+            .total_keys()
+            .last()
+            .copied()
+            .expect("hay al menos el id");
+        let ordering = a.1.cmp(&b.1);
+        if tiebreak.descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+
+    /// Compara dos alumnos según el orden del listado
+    pub fn compare_alumnos(&self, a: &PartialAlumno, b: &PartialAlumno) -> Ordering {
+        self.compare((&self.values(a), a.id), (&self.values(b), b.id))
+    }
+
+    /// `ORDER BY` del listado; `reversed` invierte cada dirección (lectura hacia atrás)
+    pub fn push_order_by<DB: Database>(
+        &self,
+        builder: &mut QueryBuilder<'static, DB>,
+        reversed: bool,
+    ) {
+        let columns: Vec<String> = self
+            .total_keys()
+            .iter()
+            .map(|key| {
+                let direction = if key.descending != reversed {
+                    "DESC"
+                } else {
+                    "ASC"
+                };
+                format!("{} {direction}", key.field.column())
+            })
+            .collect();
+        builder.push(format!(" ORDER BY {}", columns.join(", ")));
+    }
+
+    /// Condición keyset: filas que van después de `(values, id)` en el orden del listado
+    /// (o antes, si `reversed`). Con columnas `c1, c2, id` genera
+    /// `(c1 > ? OR (c1 = ? AND c2 < ?) OR (c1 = ? AND c2 = ? AND id < ?))`.
+    pub fn push_keyset<DB>(
+        &self,
+        builder: &mut QueryBuilder<'static, DB>,
+        values: &[SortValue],
+        id: i32,
+        reversed: bool,
+    ) where
+        DB: Database,
+        String: Encode<'static, DB> + Type<DB>,
+        bool: Encode<'static, DB> + Type<DB>,
+        i32: Encode<'static, DB> + Type<DB>,
+        f64: Encode<'static, DB> + Type<DB>,
+        DateTime<Utc>: Encode<'static, DB> + Type<DB>,
+    {
+        let keys = self.total_keys();
+        let mut position: Vec<SortValue> = values.to_vec();
+        if keys.len() > values.len() {
+            position.push(SortValue::Int(id));
+        }
+
+        builder.push(" AND (");
+        for (i, key) in keys.iter().enumerate() {
+            if i > 0 {
+                builder.push(" OR (");
+            }
+            for (previous, value) in keys[..i].iter().zip(&position) {
+                builder.push(format!("{} = ", previous.field.column()));
+                value.push_bind(builder);
+                builder.push(" AND ");
+            }
+            let op = if key.descending != reversed { "<" } else { ">" };
+            builder.push(format!("{} {op} ", key.field.column()));
+            position[i].push_bind(builder);
+            if i > 0 {
+                builder.push(")");
+            }
+        }
+        builder.push(")");
+    }
+}
+
+impl fmt::Display for Sort {
+    /// Forma canónica, p. ej. `apellido,-promedio`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: Vec<String> = self
+            .keys
+            .iter()
+            .map(|key| {
+                let sign = if key.descending { "-" } else { "" };
+                format!("{sign}{}", key.field.column())
+            })
+            .collect();
+        f.write_str(&items.join(","))
+    }
+}
@@ -0,0 +1,132 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use thiserror::Error;
+use validator::ValidationErrors;
+
+use crate::{
+    models::{DuplicateCandidate, ErrorResponse},
+    request_id::current_request_id,
+};
+
+/// Errores de la aplicación que se convierten en respuestas HTTP
+#[derive(Debug, Error)]
+pub enum AppError {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("Datos de entrada inválidos")]
+    Validation(Vec<String>),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    UnsupportedMediaType(String),
+
+    // El alumno cambió desde que el cliente lo leyó (If-Match no coincide)
+    #[error("{0}")]
+    PreconditionFailed(String),
+
+    // La escritura requiere If-Match (concurrency.require_if_match)
+    #[error("{0}")]
+    PreconditionRequired(String),
+
+    // Alumnos parecidos al que se quiere crear; se envían al cliente para que decida
+    #[error("El alumno podría estar registrado")]
+    PossibleDuplicates(Vec<DuplicateCandidate>),
+
+    // El detalle del error solo se registra en los logs, nunca se envía al cliente
+    #[error("Error de base de datos: {0}")]
+    Database(#[source] sqlx::Error),
+}
+
+impl AppError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Validation(_) | AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Conflict(_) | AppError::PossibleDuplicates(_) => StatusCode::CONFLICT,
+            AppError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AppError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            AppError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
+            AppError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            // Violación de constraint único: el único índice único es el email
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                AppError::Conflict("El email ya existe".to_string())
+            }
+            _ => AppError::Database(error),
+        }
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        // Ordenar por campo para que los mensajes sean estables entre requests
+        let mut fields: Vec<_> = errors.field_errors().into_iter().collect();
+        fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let messages = fields
+            .into_iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |e| match &e.message {
+                    Some(message) => message.to_string(),
+                    None => format!("Valor inválido para {field}"),
+                })
+            })
+            .collect();
+
+        AppError::Validation(messages)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+
+        let (message, errors, duplicados) = match self {
+            AppError::Validation(errors) => {
+                ("Datos de entrada inválidos".to_string(), Some(errors), None)
+            }
+            AppError::PossibleDuplicates(candidates) => (
+                "El alumno podría estar registrado; use force=true para crearlo de todos modos"
+                    .to_string(),
+                None,
+                Some(candidates),
+            ),
+            AppError::Database(e) => {
+                tracing::error!(error = %e, "Error de base de datos");
+                ("Error interno del servidor".to_string(), None, None)
+            }
+            AppError::NotFound(message)
+            | AppError::Conflict(message)
+            | AppError::BadRequest(message)
+            | AppError::UnsupportedMediaType(message)
+            | AppError::PreconditionFailed(message)
+            | AppError::PreconditionRequired(message) => (message, None, None),
+        };
+
+        (
+            status,
+            Json(ErrorResponse {
+                success: false,
+                message,
+                errors,
+                duplicados,
+                request_id: current_request_id(),
+            }),
+        )
+            .into_response()
+    }
+}
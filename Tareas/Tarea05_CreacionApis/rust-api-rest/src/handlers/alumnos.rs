@@ -1,423 +1,414 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    body::Bytes,
+    extract::{OriginalUri, Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, Uri, header},
+    response::{IntoResponse, Json, Response},
 };
-use sqlx::{MySql, Pool};
 use validator::Validate;
 
-use crate::models::*;
+use crate::{
+    database::{
+        AlumnoFilter, AlumnoPatch, DUPLICATE_BATCH, DUPLICATE_THRESHOLD, DuplicateReport,
+        EntityTags, FieldSet, Identity, IfMatch, Page, PageStart, Sort, batch_candidates_search,
+        duplicate_candidates, duplicate_pairs, etag,
+    },
+    error::AppError,
+    models::*,
+    state::AppState,
+};
+
+/// Veces que PATCH lee y aplica el patch si otra request modifica el alumno mientras tanto
+const PATCH_INTENTOS: u32 = 3;
 
-/// GET /api/alumnos - Obtener alumnos con paginación y filtros
+/// GET /api/alumnos - Obtener alumnos con paginación (por página o por cursor) y filtros
 #[utoipa::path(
     get,
     path = "/api/alumnos",
     params(
         ("page" = Option<i32>, Query, description = "Número de página (default: 1)"),
-        ("limit" = Option<i32>, Query, description = "Elementos por página (default: 10)"),
-        ("search" = Option<String>, Query, description = "Búsqueda por nombre o apellido"),
-        ("carrera" = Option<String>, Query, description = "Filtrar por carrera"),
-        ("activo" = Option<bool>, Query, description = "Filtrar por estatus activo")
+        ("limit" = Option<i32>, Query, description = "Elementos por página (default: 10, máximo: 100, configurables)"),
+        ("after" = Option<String>, Query, description = "Cursor: alumnos siguientes a next_cursor (no se combina con page)"),
+        ("before" = Option<String>, Query, description = "Cursor: alumnos anteriores a prev_cursor (no se combina con page)"),
+        ("sort" = Option<String>, Query, description = "Orden: columnas separadas por coma, `-` para descendente (ej. `apellido,-promedio`). Permitidas: id, nombre, apellido, email, edad, carrera, semestre, promedio, activo, fecha_registro, fecha_actualizacion, version. Con search también `score`. Empates por id (default: -fecha_registro; con search, -score)"),
+        ("fields" = Option<String>, Query, description = "Campos a devolver, separados por coma (ej. `id,nombre,apellido`). Permitidos: id, nombre, apellido, email, edad, carrera, semestre, promedio, activo, fecha_registro, fecha_actualizacion, version (default: todos)"),
+        ("search" = Option<String>, Query, description = "Búsqueda de texto completo en nombre, apellido, email y carrera, sin acentos ni mayúsculas; cada palabra se busca como prefijo y deben aparecer todas. Agrega `score` (relevancia) a cada alumno"),
+        ("carrera" = Option<String>, Query, description = "Filtrar por carrera; varias separadas por coma"),
+        ("activo" = Option<bool>, Query, description = "Filtrar por estatus activo"),
+        ("edad_min" = Option<i32>, Query, description = "Edad mínima (inclusive)"),
+        ("edad_max" = Option<i32>, Query, description = "Edad máxima (inclusive)"),
+        ("semestre_min" = Option<i32>, Query, description = "Semestre mínimo (inclusive)"),
+        ("semestre_max" = Option<i32>, Query, description = "Semestre máximo (inclusive)"),
+        ("promedio_min" = Option<f64>, Query, description = "Promedio mínimo (inclusive)"),
+        ("promedio_max" = Option<f64>, Query, description = "Promedio máximo (inclusive)"),
+        ("registrado_desde" = Option<DateTime<Utc>>, Query, description = "Registrados desde esta fecha (RFC 3339, inclusive)"),
+        ("registrado_hasta" = Option<DateTime<Utc>>, Query, description = "Registrados hasta esta fecha (RFC 3339, inclusive)"),
+        ("email_dominio" = Option<String>, Query, description = "Dominio del email (ej. `universidad.edu`); varios separados por coma")
     ),
     responses(
-        (status = 200, description = "Lista de alumnos", body = AlumnosResponse),
+        (status = 200, description = "Lista de alumnos", body = AlumnosResponse,
+            headers(("Link" = String, description = "Enlaces self, first, prev, next y last (RFC 8288)"))),
+        (status = 400, description = "Rango de filtros invertido, campo, orden o cursor inválido, o cursor combinado con page", body = ErrorResponse),
         (status = 500, description = "Error interno del servidor", body = ErrorResponse)
     ),
     tag = "Alumnos"
 )]
+#[tracing::instrument(skip_all)]
 pub async fn get_alumnos(
-    State(pool): State<Pool<MySql>>,     
+    State(state): State<AppState>,
+    OriginalUri(uri): OriginalUri, // URI completa (con /api) para los enlaces
     Query(params): Query<PaginationQuery>, // Extractor de query parameters
-) -> Result<Json<AlumnosResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Calcular paginación con valores por defecto y límites
-    let page = params.page.unwrap_or(1).max(1);
-    let limit = params.limit.unwrap_or(10).min(100).max(1);
-    let offset = (page - 1) * limit;
-
-    // Construir queries dinámicamente basado en filtros
-    let mut query = "SELECT * FROM alumnos WHERE 1=1".to_string();
-    let mut count_query = "SELECT COUNT(*) as total FROM alumnos WHERE 1=1".to_string();
-    
-    // Agregar filtro de búsqueda si se proporciona
-    if let Some(search) = &params.search {
-        let search_condition = format!(" AND (nombre LIKE '%{}%' OR apellido LIKE '%{}%')", search, search);
-        query.push_str(&search_condition);
-        count_query.push_str(&search_condition);
-    }
-    
-    // Agregar filtro por carrera
-    if let Some(carrera) = &params.carrera {
-        let carrera_condition = format!(" AND carrera = '{}'", carrera);
-        query.push_str(&carrera_condition);
-        count_query.push_str(&carrera_condition);
-    }
-    
-    // Agregar filtro por estado activo
-    if let Some(activo) = params.activo {
-        let activo_condition = format!(" AND activo = {}", activo);
-        query.push_str(&activo_condition);
-        count_query.push_str(&activo_condition);
+) -> Result<(HeaderMap, Json<AlumnosResponse>), AppError> {
+    // Los rangos de los filtros (mínimo/máximo, desde/hasta) no pueden estar invertidos
+    params.validate()?;
+
+    // Calcular paginación con los valores por defecto y límites de la configuración
+    let limit = params
+        .limit
+        .map_or(state.pagination.default_limit, i64::from)
+        .clamp(1, state.pagination.max_limit);
+    // Filtros con parámetros bindeados a partir de los query params
+    let filter = AlumnoFilter::from(&params);
+
+    // Una búsqueda se ordena por relevancia salvo que se pida otro orden
+    let sort = Sort::from_query(params.sort.as_deref(), filter.search.is_some())?;
+    let fields = FieldSet::parse(params.fields.as_deref())?;
+    let start = PageStart::from_query(&params, &sort, limit)?;
+
+    // Una fila extra indica si hay más alumnos después de la página
+    let rows = state
+        .alumnos
+        .list(&filter, &fields, &sort, &start, limit + 1)
+        .await?;
+    let mut page = start.page(rows, &sort, limit)?;
+    // Las columnas del orden se leen para los cursores aunque no se hayan pedido
+    let alumnos = std::mem::take(&mut page.alumnos)
+        .into_iter()
+        .map(|alumno| fields.project(alumno))
+        .collect();
+    let total = state.alumnos.count(&filter).await?;
+    let total_pages = (total + limit - 1) / limit;
+    let page_number = match start {
+        PageStart::Offset(offset) => Some(offset / limit + 1),
+        _ => None,
+    };
+    let links = page_links(&uri, page_number, &page, total_pages);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::LINK,
+        HeaderValue::from_str(&links.header()).expect("los enlaces salen de una URI válida"),
+    );
+
+    Ok((
+        headers,
+        Json(AlumnosResponse {
+            success: true,
+            message: "Alumnos obtenidos exitosamente".to_string(),
+            data: alumnos,
+            total,
+            page: page_number,
+            limit,
+            total_pages,
+            has_next: page.has_next,
+            has_prev: page.has_prev,
+            next_cursor: page.next_cursor,
+            prev_cursor: page.prev_cursor,
+            links,
+        }),
+    ))
+}
+
+/// Enlaces de navegación: en el modo por páginas `prev`/`next` cambian `page`; en el modo
+/// por cursor usan `before`/`after`. `first` y `last` siempre son páginas numeradas.
+/// Los demás parámetros de la request (filtros, orden, límite) se conservan.
+fn page_links(uri: &Uri, page_number: Option<i64>, page: &Page, total_pages: i64) -> PageLinks {
+    let link = |param: &str, value: &str| {
+        let mut pairs: Vec<&str> = uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| {
+                let key = pair.split_once('=').map_or(*pair, |(key, _)| key);
+                !pair.is_empty() && !matches!(key, "page" | "after" | "before")
+            })
+            .collect();
+        let position = format!("{param}={value}");
+        pairs.push(&position);
+        format!("{}?{}", uri.path(), pairs.join("&"))
+    };
+
+    let (prev, next) = match page_number {
+        Some(number) => (
+            page.has_prev
+                .then(|| link("page", &(number - 1).to_string())),
+            page.has_next
+                .then(|| link("page", &(number + 1).to_string())),
+        ),
+        None => (
+            page.prev_cursor
+                .as_deref()
+                .map(|cursor| link("before", cursor)),
+            page.next_cursor
+                .as_deref()
+                .map(|cursor| link("after", cursor)),
+        ),
+    };
+
+    PageLinks {
+        current: uri
+            .path_and_query()
+            .map_or_else(|| uri.path().to_string(), ToString::to_string),
+        first: link("page", "1"),
+        last: link("page", &total_pages.max(1).to_string()),
+        next,
+        prev,
     }
+}
 
-    // Agregar ordenamiento y paginación
-    query.push_str(" ORDER BY fecha_registro DESC");
-    query.push_str(&format!(" LIMIT {} OFFSET {}", limit, offset));
-
-    // Ejecutar query principal para obtener alumnos
-    match sqlx::query_as::<_, Alumno>(&query).fetch_all(&pool).await {
-        Ok(alumnos) => {
-            // Ejecutar query de conteo para total de registros
-            let total: (i64,) = sqlx::query_as(&count_query).fetch_one(&pool).await
-                .unwrap_or((0,));
-
-            Ok(Json(AlumnosResponse {
-                success: true,
-                message: "Alumnos obtenidos exitosamente".to_string(),
-                data: alumnos,
-                total: total.0,
-            }))
-        }
-        Err(e) => {
-            tracing::error!("Error al obtener alumnos: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "Error interno del servidor".to_string(),
-                    errors: Some(vec![e.to_string()]),
-                }),
-            ))
-        }
+/// GET /api/alumnos/duplicados - Pares de alumnos que podrían ser la misma persona
+#[utoipa::path(
+    get,
+    path = "/api/alumnos/duplicados",
+    params(
+        ("umbral" = Option<f64>, Query, description = "Similitud mínima de un par, de 0.0 a 1.0 (default: 0.8)"),
+        ("limit" = Option<i32>, Query, description = "Pares a devolver (default: 10, máximo: 100, configurables)")
+    ),
+    responses(
+        (status = 200, description = "Posibles duplicados, los más parecidos primero", body = DuplicatesResponse),
+        (status = 400, description = "Umbral fuera de rango", body = ErrorResponse),
+        (status = 500, description = "Error interno del servidor", body = ErrorResponse)
+    ),
+    tag = "Alumnos"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn get_duplicados(
+    State(state): State<AppState>,
+    Query(params): Query<DuplicatesQuery>,
+) -> Result<Json<DuplicatesResponse>, AppError> {
+    params.validate()?;
+
+    // Mismos límites que el listado de alumnos
+    let limit = params
+        .limit
+        .map_or(state.pagination.default_limit, i64::from)
+        .clamp(1, state.pagination.max_limit);
+
+    // La tabla se lee por lotes, y cada lote solo se compara con los registrados cuyos
+    // nombres empiezan igual (ver duplicate_pairs)
+    let threshold = params.umbral.unwrap_or(DUPLICATE_THRESHOLD);
+    let mut report = DuplicateReport::new(limit);
+    let mut after = 0;
+    loop {
+        let alumnos = state.alumnos.batch(after, DUPLICATE_BATCH).await?;
+        let Some(last) = alumnos.last() else {
+            break;
+        };
+        after = last.id;
+        let candidatos = state.alumnos.matching(&batch_candidates_search(&alumnos)).await?;
+        report.extend(duplicate_pairs(&alumnos, &candidatos, threshold));
     }
+    let total = report.total();
+
+    Ok(Json(DuplicatesResponse {
+        success: true,
+        message: "Posibles duplicados obtenidos exitosamente".to_string(),
+        data: report.into_pairs(),
+        total,
+        limit,
+    }))
 }
 
 /// POST /api/alumnos - Crear nuevo alumno
 #[utoipa::path(
     post,
     path = "/api/alumnos",
+    params(
+        ("force" = Option<bool>, Query, description = "Crear sin buscar alumnos parecidos registrados (default: false)")
+    ),
     request_body = CreateAlumnoRequest,
     responses(
-        (status = 201, description = "Alumno creado exitosamente", body = AlumnoResponse),
+        (status = 201, description = "Alumno creado exitosamente; los alumnos parecidos se informan en `duplicados`", body = AlumnoResponse,
+            headers(("ETag" = String, description = "Versión del alumno creado"))),
         (status = 400, description = "Datos inválidos", body = ErrorResponse),
-        (status = 409, description = "Email ya existe", body = ErrorResponse),
+        (status = 409, description = "Email ya existe, o hay posibles duplicados (en `duplicados`) con duplicates.block_on_create y no se usó force=true", body = ErrorResponse),
         (status = 500, description = "Error interno del servidor", body = ErrorResponse)
     ),
     tag = "Alumnos"
 )]
+#[tracing::instrument(skip_all)]
 pub async fn create_alumno(
-    State(pool): State<Pool<MySql>>,           // Pool 
+    State(state): State<AppState>,
+    Query(params): Query<CreateAlumnoQuery>,
     Json(payload): Json<CreateAlumnoRequest>,  // Deserialización automática del JSON
-) -> Result<Json<AlumnoResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<(HeaderMap, Json<AlumnoResponse>), AppError> {
     // Validar datos usando las reglas definidas en el struct
-    if let Err(errors) = payload.validate() {
-        let error_messages: Vec<String> = errors
-            .field_errors()
-            .into_iter()
-            .flat_map(|(_, v)| v.into_iter().map(|e| e.message.as_ref().unwrap().to_string()))
-            .collect();
+    payload.validate()?;
 
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                success: false,
-                message: "Datos de entrada inválidos".to_string(),
-                errors: Some(error_messages),
-            }),
-        ));
-    }
+    // Un alumno parecido a otro ya registrado suele ser la misma persona con un error de
+    // tipeo. Solo se leen los registrados cuyos nombres empiezan igual; force=true omite
+    // la búsqueda.
+    let candidates = if params.force.unwrap_or(false) {
+        Vec::new()
+    } else {
+        let identity = Identity::from(&payload);
+        let registrados = state.alumnos.matching(&identity.candidates_search()).await?;
+        duplicate_candidates(identity, registrados, DUPLICATE_THRESHOLD)
+    };
 
-    // Usar valor por defecto para promedio si no se proporciona
-    let promedio = payload.promedio.unwrap_or(0.0);
-
-    // Prepared statement para prevenir SQL injection
-    let query = r#"
-        INSERT INTO alumnos (nombre, apellido, email, edad, carrera, semestre, promedio)
-        VALUES (?, ?, ?, ?, ?, ?, ?)
-    "#;
-
-    // Ejecutar insert con parámetros bindeados
-    match sqlx::query(query)
-        .bind(&payload.nombre)
-        .bind(&payload.apellido)
-        .bind(&payload.email)
-        .bind(payload.edad)
-        .bind(&payload.carrera)
-        .bind(payload.semestre)
-        .bind(promedio)
-        .execute(&pool)
-        .await
-    {
-        Ok(result) => {
-            // Obtener ID del registro insertado
-            let alumno_id = result.last_insert_id() as i32;
-
-            // Fetch del alumno recién creado para retornarlo
-            match sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ?")
-                .bind(alumno_id)
-                .fetch_one(&pool)
-                .await
-            {
-                Ok(alumno) => Ok(Json(AlumnoResponse {
-                    success: true,
-                    message: "Alumno creado exitosamente".to_string(),
-                    data: Some(alumno),
-                })),
-                Err(e) => {
-                    tracing::error!("Error al obtener alumno creado: {}", e);
-                    Err((
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(ErrorResponse {
-                            success: false,
-                            message: "Error interno del servidor".to_string(),
-                            errors: Some(vec![e.to_string()]),
-                        }),
-                    ))
-                }
-            }
-        }
-        // Manejo específico de violación de constraint único (email duplicado)
-        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
-            Err((
-                StatusCode::CONFLICT,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "El email ya existe".to_string(),
-                    errors: None,
-                }),
-            ))
-        }
-        Err(e) => {
-            tracing::error!("Error al crear alumno: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "Error interno del servidor".to_string(),
-                    errors: Some(vec![e.to_string()]),
-                }),
-            ))
-        }
+    // Con duplicates.block_on_create se rechaza con las sugerencias; si no, solo se avisa
+    if state.duplicates.block_on_create && !candidates.is_empty() {
+        return Err(AppError::PossibleDuplicates(candidates));
     }
+
+    // Insertar y obtener el alumno creado; un email duplicado se convierte en 409
+    let alumno = state.alumnos.create(&payload).await?;
+
+    let message = if candidates.is_empty() {
+        "Alumno creado exitosamente"
+    } else {
+        "Alumno creado exitosamente; hay alumnos parecidos registrados (ver duplicados)"
+    };
+
+    Ok((
+        etag_header(alumno.version),
+        Json(AlumnoResponse {
+            success: true,
+            message: message.to_string(),
+            data: Some(alumno),
+            duplicados: (!candidates.is_empty()).then_some(candidates),
+        }),
+    ))
 }
 
-/// PUT /api/alumnos/{id} - Actualizar alumno existente
+/// PUT /api/alumnos/{id} - Reemplazar un alumno existente
 #[utoipa::path(
     put,
     path = "/api/alumnos/{id}",
     params(
-        ("id" = i32, Path, description = "ID del alumno")
+        ("id" = i32, Path, description = "ID del alumno"),
+        ("If-Match" = Option<String>, Header, description = "ETag leído del alumno; si cambió desde entonces responde 412 (obligatorio con concurrency.require_if_match)")
     ),
-    request_body = UpdateAlumnoRequest,
+    request_body(content = CreateAlumnoRequest, description = "Representación completa del alumno: todos los campos de creación son obligatorios (promedio omitido = 0.0)"),
     responses(
-        (status = 200, description = "Alumno actualizado exitosamente", body = AlumnoResponse),
+        (status = 200, description = "Alumno actualizado exitosamente", body = AlumnoResponse,
+            headers(("ETag" = String, description = "Nueva versión del alumno"))),
         (status = 400, description = "Datos inválidos", body = ErrorResponse),
         (status = 404, description = "Alumno no encontrado", body = ErrorResponse),
         (status = 409, description = "Email ya existe", body = ErrorResponse),
+        (status = 412, description = "El alumno cambió desde que se leyó o no existe (If-Match no coincide)", body = ErrorResponse),
+        (status = 422, description = "Faltan campos o tienen un tipo incorrecto"),
+        (status = 428, description = "Falta If-Match y la configuración lo exige", body = ErrorResponse),
         (status = 500, description = "Error interno del servidor", body = ErrorResponse)
     ),
     tag = "Alumnos"
 )]
+#[tracing::instrument(skip(state, headers, payload))]
 pub async fn update_alumno(
-    State(pool): State<Pool<MySql>>,
-    Path(id): Path<i32>,                      
-    Json(payload): Json<UpdateAlumnoRequest>,
-) -> Result<Json<AlumnoResponse>, (StatusCode, Json<ErrorResponse>)> {
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+    Json(payload): Json<CreateAlumnoRequest>,  // Sin campos opcionales: reemplazo completo
+) -> Result<(HeaderMap, Json<AlumnoResponse>), AppError> {
     // Validar datos de entrada
-    if let Err(errors) = payload.validate() {
-        let error_messages: Vec<String> = errors
-            .field_errors()
-            .into_iter()
-            .flat_map(|(_, v)| v.into_iter().map(|e| e.message.as_ref().unwrap().to_string()))
-            .collect();
+    payload.validate()?;
 
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                success: false,
-                message: "Datos de entrada inválidos".to_string(),
-                errors: Some(error_messages),
-            }),
-        ));
-    }
+    // Con If-Match solo se reemplaza la versión que el cliente leyó
+    let (if_match, version) = expected_version(&state, id, &headers).await?;
 
-    // Verificar que el alumno existe antes de actualizar
-    let existing_alumno = sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ?")
-        .bind(id)
-        .fetch_optional(&pool)
-        .await;
-
-    match existing_alumno {
-        Ok(Some(_)) => {}
-        Ok(None) => {
-            return Err((
-                StatusCode::NOT_FOUND,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "Alumno no encontrado".to_string(),
-                    errors: None,
-                }),
-            ))
-        }
-        Err(e) => {
-            tracing::error!("Error al verificar alumno: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "Error interno del servidor".to_string(),
-                    errors: Some(vec![e.to_string()]),
-                }),
-            ));
-        }
-    }
-
-    // Construir query UPDATE dinámicamente - solo campos proporcionados
-    let mut query_builder = sqlx::QueryBuilder::new("UPDATE alumnos SET ");
-    let mut has_updates = false;
+    // Reemplazar todos los campos; activo solo se cambia con PATCH
+    let alumno = state
+        .alumnos
+        .update(id, &UpdateAlumnoRequest::from(&payload), version)
+        .await?
+        .ok_or_else(|| if_match.not_found())?;
 
-    // Agregar cada campo solo si está presente en el payload
-    if let Some(nombre) = &payload.nombre {
-        if has_updates {
-            query_builder.push(", ");
-        }
-        query_builder.push("nombre = ").push_bind(nombre);
-        has_updates = true;
-    }
-    
-    if let Some(apellido) = &payload.apellido {
-        if has_updates {
-            query_builder.push(", ");
-        }
-        query_builder.push("apellido = ").push_bind(apellido);
-        has_updates = true;
-    }
-    
-    if let Some(email) = &payload.email {
-        if has_updates {
-            query_builder.push(", ");
-        }
-        query_builder.push("email = ").push_bind(email);
-        has_updates = true;
-    }
-    
-    if let Some(edad) = payload.edad {
-        if has_updates {
-            query_builder.push(", ");
-        }
-        query_builder.push("edad = ").push_bind(edad);
-        has_updates = true;
-    }
-    
-    if let Some(carrera) = &payload.carrera {
-        if has_updates {
-            query_builder.push(", ");
-        }
-        query_builder.push("carrera = ").push_bind(carrera);
-        has_updates = true;
-    }
-    
-    if let Some(semestre) = payload.semestre {
-        if has_updates {
-            query_builder.push(", ");
-        }
-        query_builder.push("semestre = ").push_bind(semestre);
-        has_updates = true;
-    }
-    
-    if let Some(promedio) = payload.promedio {
-        if has_updates {
-            query_builder.push(", ");
-        }
-        query_builder.push("promedio = ").push_bind(promedio);
-        has_updates = true;
-    }
-    
-    // Campo activo - tipo bool se maneja correctamente
-    if let Some(activo) = payload.activo {
-        if has_updates {
-            query_builder.push(", ");
-        }
-        query_builder.push("activo = ").push_bind(activo); 
-        has_updates = true;
-    }
+    Ok((
+        etag_header(alumno.version),
+        Json(AlumnoResponse {
+            success: true,
+            message: "Alumno actualizado exitosamente".to_string(),
+            data: Some(alumno),
+            duplicados: None,
+        }),
+    ))
+}
 
-    // Validar que al menos un campo se proporcionó para actualizar
-    if !has_updates {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ErrorResponse {
-                success: false,
-                message: "No hay campos para actualizar".to_string(),
-                errors: None,
-            }),
-        ));
-    }
+/// PATCH /api/alumnos/{id} - Actualización parcial con JSON Merge Patch o JSON Patch
+#[utoipa::path(
+    patch,
+    path = "/api/alumnos/{id}",
+    params(
+        ("id" = i32, Path, description = "ID del alumno"),
+        ("If-Match" = Option<String>, Header, description = "ETag leído del alumno; si cambió desde entonces responde 412 (obligatorio con concurrency.require_if_match)")
+    ),
+    request_body(
+        description = "JSON Merge Patch (RFC 7396; también como application/json) o JSON Patch (RFC 6902) sobre el alumno",
+        content(
+            (UpdateAlumnoRequest = "application/merge-patch+json"),
+            (json_patch::Patch = "application/json-patch+json")
+        )
+    ),
+    responses(
+        (status = 200, description = "Alumno actualizado exitosamente", body = AlumnoResponse,
+            headers(("ETag" = String, description = "Nueva versión del alumno"))),
+        (status = 400, description = "Patch inválido, campo borrado, desconocido o de solo lectura, o datos inválidos", body = ErrorResponse),
+        (status = 404, description = "Alumno no encontrado", body = ErrorResponse),
+        (status = 409, description = "Email ya existe, o una operación test no se cumplió", body = ErrorResponse),
+        (status = 412, description = "El alumno cambió desde que se leyó o no existe (If-Match no coincide)", body = ErrorResponse),
+        (status = 415, description = "Content-Type no soportado", body = ErrorResponse),
+        (status = 428, description = "Falta If-Match y la configuración lo exige", body = ErrorResponse),
+        (status = 500, description = "Error interno del servidor", body = ErrorResponse)
+    ),
+    tag = "Alumnos"
+)]
+#[tracing::instrument(skip(state, headers, body))]
+pub async fn patch_alumno(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+    body: Bytes, // El formato depende del Content-Type
+) -> Result<(HeaderMap, Json<AlumnoResponse>), AppError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let patch = AlumnoPatch::parse(content_type, &body)?;
+    let if_match = IfMatch::from_headers(&headers, state.concurrency.require_if_match)?;
 
-    // Agregar WHERE clause
-    query_builder.push(" WHERE id = ").push_bind(id);
-
-    // Ejecutar update
-    match query_builder.build().execute(&pool).await {
-        Ok(result) => {
-            // Verificar que se actualizó al menos una fila
-            if result.rows_affected() == 0 {
-                return Err((
-                    StatusCode::NOT_FOUND,
-                    Json(ErrorResponse {
-                        success: false,
-                        message: "Alumno no encontrado".to_string(),
-                        errors: None,
-                    }),
-                ));
-            }
+    // El patch se aplica sobre el alumno leído y solo se guarda si sigue en esa versión:
+    // una escritura concurrente no se pisa ni los `test` se evalúan sobre datos viejos.
+    // Sin If-Match (o con `*`) se vuelve a leer y aplicar; con un ETag es un 412.
+    let mut intentos = 1;
+    let alumno = loop {
+        let alumno = state
+            .alumnos
+            .get(id)
+            .await?
+            .ok_or_else(|| if_match.not_found())?;
+        if_match.expected_version(alumno.version)?;
+        let changes = patch.changes(&alumno)?;
+        changes.validate()?;
 
-            // Retornar el alumno actualizado
-            match sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ?")
-                .bind(id)
-                .fetch_one(&pool)
-                .await
+        match state.alumnos.update(id, &changes, Some(alumno.version)).await {
+            Err(AppError::PreconditionFailed(_))
+                if !if_match.needs_version() && intentos < PATCH_INTENTOS =>
             {
-                Ok(alumno) => Ok(Json(AlumnoResponse {
-                    success: true,
-                    message: "Alumno actualizado exitosamente".to_string(),
-                    data: Some(alumno),
-                })),
-                Err(e) => {
-                    tracing::error!("Error al obtener alumno actualizado: {}", e);
-                    Err((
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(ErrorResponse {
-                            success: false,
-                            message: "Error interno del servidor".to_string(),
-                            errors: Some(vec![e.to_string()]),
-                        }),
-                    ))
-                }
+                intentos += 1;
+            }
+            result => {
+                break result?.ok_or_else(|| if_match.not_found())?;
             }
         }
-        // Manejo de constraint violation en updates (email duplicado)
-        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
-            Err((
-                StatusCode::CONFLICT,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "El email ya existe".to_string(),
-                    errors: None,
-                }),
-            ))
-        }
-        Err(e) => {
-            tracing::error!("Error al actualizar alumno: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "Error interno del servidor".to_string(),
-                    errors: Some(vec![e.to_string()]),
-                }),
-            ))
-        }
-    }
+    };
+
+    Ok((
+        etag_header(alumno.version),
+        Json(AlumnoResponse {
+            success: true,
+            message: "Alumno actualizado exitosamente".to_string(),
+            data: Some(alumno),
+            duplicados: None,
+        }),
+    ))
 }
 
 /// Obtener un alumno por ID
@@ -425,49 +416,53 @@ pub async fn update_alumno(
     get,
     path = "/api/alumnos/{id}",
     params(
-        ("id" = i32, Path, description = "ID del alumno")
+        ("id" = i32, Path, description = "ID del alumno"),
+        ("fields" = Option<String>, Query, description = "Campos a devolver, separados por coma (ej. `id,nombre,apellido`). Permitidos: id, nombre, apellido, email, edad, carrera, semestre, promedio, activo, fecha_registro, fecha_actualizacion, version (default: todos)"),
+        ("If-None-Match" = Option<String>, Header, description = "ETag de una lectura anterior; si el alumno no cambió responde 304 sin cuerpo")
     ),
     responses(
-        (status = 200, description = "Alumno encontrado", body = AlumnoResponse),
+        (status = 200, description = "Alumno encontrado", body = PartialAlumnoResponse,
+            headers(("ETag" = String, description = "Versión del alumno, para If-Match e If-None-Match"))),
+        (status = 304, description = "El alumno no cambió desde la versión de If-None-Match",
+            headers(("ETag" = String, description = "Versión del alumno"))),
+        (status = 400, description = "Campo no permitido en fields", body = ErrorResponse),
         (status = 404, description = "Alumno no encontrado", body = ErrorResponse),
         (status = 500, description = "Error interno del servidor", body = ErrorResponse)
     ),
     tag = "Alumnos"
 )]
+#[tracing::instrument(skip(state, headers))]
 pub async fn get_alumno(
-    State(pool): State<Pool<MySql>>,
-    Path(id): Path<i32>,  
-) -> Result<Json<AlumnoResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ?")
-        .bind(id)  
-        .fetch_optional(&pool)
-        .await
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<FieldsQuery>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let fields = FieldSet::parse(params.fields.as_deref())?;
+    let alumno = state
+        .alumnos
+        .get_fields(id, &fields)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Alumno no encontrado".to_string()))?;
+    // La versión se lee siempre para el ETag, aunque no se haya pedido en fields
+    let version = alumno.version.expect("get_fields siempre lee la versión");
+
+    // El cliente ya tiene esta versión: 304 sin cuerpo
+    if EntityTags::from_headers(&headers, &header::IF_NONE_MATCH)
+        .is_some_and(|tags| tags.matches_weak(version))
     {
-        Ok(Some(alumno)) => Ok(Json(AlumnoResponse {
+        return Ok((StatusCode::NOT_MODIFIED, etag_header(version)).into_response());
+    }
+
+    Ok((
+        etag_header(version),
+        Json(PartialAlumnoResponse {
             success: true,
             message: "Alumno encontrado".to_string(),
-            data: Some(alumno),
-        })),
-        Ok(None) => Err((
-            StatusCode::NOT_FOUND,
-            Json(ErrorResponse {
-                success: false,
-                message: "Alumno no encontrado".to_string(),
-                errors: None,
-            }),
-        )),
-        Err(e) => {
-            tracing::error!("Error al obtener alumno: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "Error interno del servidor".to_string(),
-                    errors: Some(vec![e.to_string()]),
-                }),
-            ))
-        }
-    }
+            data: Some(fields.project(alumno)),
+        }),
+    )
+        .into_response())
 }
 
 /// Eliminar un alumno
@@ -475,52 +470,63 @@ pub async fn get_alumno(
     delete,
     path = "/api/alumnos/{id}",
     params(
-        ("id" = i32, Path, description = "ID del alumno")
+        ("id" = i32, Path, description = "ID del alumno"),
+        ("If-Match" = Option<String>, Header, description = "ETag leído del alumno; si cambió desde entonces responde 412 (obligatorio con concurrency.require_if_match)")
     ),
     responses(
         (status = 200, description = "Alumno eliminado exitosamente", body = AlumnoResponse),
         (status = 404, description = "Alumno no encontrado", body = ErrorResponse),
+        (status = 412, description = "El alumno cambió desde que se leyó o no existe (If-Match no coincide)", body = ErrorResponse),
+        (status = 428, description = "Falta If-Match y la configuración lo exige", body = ErrorResponse),
         (status = 500, description = "Error interno del servidor", body = ErrorResponse)
     ),
     tag = "Alumnos"
 )]
+#[tracing::instrument(skip(state, headers))]
 pub async fn delete_alumno(
-    State(pool): State<Pool<MySql>>,
-    Path(id): Path<i32>,  // Cambiado de String a i32
-) -> Result<Json<AlumnoResponse>, (StatusCode, Json<ErrorResponse>)> {
-    match sqlx::query("DELETE FROM alumnos WHERE id = ?")
-        .bind(id)
-        .execute(&pool)
-        .await
-    {
-        Ok(result) => {
-            if result.rows_affected() == 0 {
-                Err((
-                    StatusCode::NOT_FOUND,
-                    Json(ErrorResponse {
-                        success: false,
-                        message: "Alumno no encontrado".to_string(),
-                        errors: None,
-                    }),
-                ))
-            } else {
-                Ok(Json(AlumnoResponse {
-                    success: true,
-                    message: "Alumno eliminado exitosamente".to_string(),
-                    data: None,
-                }))
-            }
-        }
-        Err(e) => {
-            tracing::error!("Error al eliminar alumno: {}", e);
-            Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    success: false,
-                    message: "Error interno del servidor".to_string(),
-                    errors: Some(vec![e.to_string()]),
-                }),
-            ))
-        }
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+) -> Result<Json<AlumnoResponse>, AppError> {
+    // Con If-Match solo se elimina la versión que el cliente leyó
+    let (if_match, version) = expected_version(&state, id, &headers).await?;
+
+    if !state.alumnos.delete(id, version).await? {
+        return Err(if_match.not_found());
     }
-}
\ No newline at end of file
+
+    Ok(Json(AlumnoResponse {
+        success: true,
+        message: "Alumno eliminado exitosamente".to_string(),
+        data: None,
+        duplicados: None,
+    }))
+}
+
+/// Cabecera ETag con la versión del alumno
+fn etag_header(version: i32) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(header::ETAG, etag(version));
+    headers
+}
+
+/// Condición If-Match y versión que debe seguir teniendo el alumno al escribirlo (`None`
+/// sin condición). Solo se lee el alumno cuando la condición lista ETags.
+async fn expected_version(
+    state: &AppState,
+    id: i32,
+    headers: &HeaderMap,
+) -> Result<(IfMatch, Option<i32>), AppError> {
+    let if_match = IfMatch::from_headers(headers, state.concurrency.require_if_match)?;
+    if !if_match.needs_version() {
+        return Ok((if_match, None));
+    }
+
+    let alumno = state
+        .alumnos
+        .get(id)
+        .await?
+        .ok_or_else(|| if_match.not_found())?;
+    let version = if_match.expected_version(alumno.version)?;
+    Ok((if_match, version))
+}
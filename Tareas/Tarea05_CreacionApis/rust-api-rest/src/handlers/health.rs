@@ -0,0 +1,102 @@
+use std::time::Instant;
+
+use axum::{extract::State, http::StatusCode, response::Json};
+use chrono::Utc;
+
+use crate::{models::*, state::AppState};
+
+/// GET /health/live - El proceso está vivo (no consulta dependencias)
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    responses(
+        (status = 200, description = "El proceso atiende requests", body = LivenessResponse)
+    ),
+    tag = "Health"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn liveness(State(state): State<AppState>) -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        status: "ok".to_string(),
+        message: "API de Alumnos funcionando correctamente".to_string(),
+        timestamp: Utc::now().to_rfc3339(),
+        version: "1.0.0".to_string(),
+        uptime_secs: state.started_at.elapsed().as_secs(),
+    })
+}
+
+/// GET /health/ready - La API puede recibir tráfico: la base de datos responde
+/// y no se está apagando
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    responses(
+        (status = 200, description = "Todas las dependencias responden", body = ReadinessResponse),
+        (status = 503, description = "Alguna dependencia no responde o la API se está apagando", body = ReadinessResponse)
+    ),
+    tag = "Health"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn readiness(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    // SELECT 1 con tiempo máximo: un pool agotado o una red caída no deben colgar la verificación
+    let started = Instant::now();
+    let ping = tokio::time::timeout(state.ping_timeout, state.alumnos.ping()).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    let timed_out = || {
+        let error = format!(
+            "Sin respuesta de la base de datos en {} ms",
+            state.ping_timeout.as_millis()
+        );
+        tracing::warn!(error = %error, "❌ Readiness: la base de datos no responde");
+        Some(error)
+    };
+
+    let mut error = match ping {
+        Ok(Ok(())) => None,
+        // El detalle del motor (host, usuario, mensaje del driver) solo va a los logs;
+        // el endpoint es público
+        Ok(Err(e)) => {
+            tracing::error!(error = %e, "❌ Readiness: la base de datos no responde");
+            Some("Base de datos no disponible".to_string())
+        }
+        Err(_) => timed_out(),
+    };
+
+    // La versión de migraciones es informativa: si falla la API sigue lista, pero con el
+    // mismo tiempo máximo que el SELECT 1; sin respuesta a tiempo la API no está lista
+    let mut migration_version = None;
+    if error.is_none() {
+        match tokio::time::timeout(state.ping_timeout, state.alumnos.schema_version()).await {
+            Ok(Ok(version)) => migration_version = version,
+            Ok(Err(e)) => tracing::warn!(error = %e, "No se pudo leer la versión de migraciones"),
+            Err(_) => error = timed_out(),
+        }
+    }
+    let healthy = error.is_none();
+
+    // Durante el apagado se deja de recibir tráfico aunque la base responda
+    let (status, label) = if state.is_shutting_down() {
+        (StatusCode::SERVICE_UNAVAILABLE, "shutting_down")
+    } else if healthy {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "unavailable")
+    };
+
+    (
+        status,
+        Json(ReadinessResponse {
+            status: label.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            uptime_secs: state.started_at.elapsed().as_secs(),
+            database: DependencyCheck {
+                healthy,
+                latency_ms,
+                error,
+            },
+            pool: state.alumnos.pool_stats(),
+            migration_version,
+        }),
+    )
+}
@@ -0,0 +1,52 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+use metrics::gauge;
+
+use crate::{database::AlumnoFilter, metrics::prometheus_handle, state::AppState};
+
+/// GET /metrics - Métricas en formato de texto de Prometheus
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Métricas en formato de texto de Prometheus", body = String, content_type = "text/plain")
+    ),
+    tag = "Health"
+)]
+#[tracing::instrument(skip_all)]
+pub async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    record_gauges(&state).await;
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        prometheus_handle().render(),
+    )
+}
+
+/// Actualiza los gauges del pool y de negocio justo antes de exportarlos.
+/// Cada consulta tiene el mismo tiempo máximo que readiness; si falla se conserva
+/// el último valor y se registra el error.
+async fn record_gauges(state: &AppState) {
+    if let Some(stats) = state.alumnos.pool_stats() {
+        gauge!("db_pool_connections", "state" => "in_use")
+            .set(f64::from(stats.size.saturating_sub(stats.idle)));
+        gauge!("db_pool_connections", "state" => "idle").set(f64::from(stats.idle));
+        gauge!("db_pool_max_connections").set(f64::from(stats.max_connections));
+    }
+
+    let activos = AlumnoFilter {
+        activo: Some(true),
+        ..Default::default()
+    };
+    for (name, filter) in [
+        ("alumnos_registrados", AlumnoFilter::default()),
+        ("alumnos_activos", activos),
+    ] {
+        match tokio::time::timeout(state.ping_timeout, state.alumnos.count(&filter)).await {
+            Ok(Ok(total)) => gauge!(name).set(total as f64),
+            Ok(Err(e)) => {
+                tracing::warn!(error = %e, metric = name, "No se pudo calcular la métrica")
+            }
+            Err(_) => tracing::warn!(metric = name, "Tiempo agotado al calcular la métrica"),
+        }
+    }
+}
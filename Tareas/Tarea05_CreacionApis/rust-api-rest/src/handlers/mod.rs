@@ -1,2 +1,6 @@
 pub mod alumnos;
-pub use alumnos::*;
\ No newline at end of file
+pub mod health;
+pub mod metrics;
+pub use alumnos::*;
+pub use health::*;
+pub use metrics::*;
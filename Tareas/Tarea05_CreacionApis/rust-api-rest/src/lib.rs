@@ -1,3 +1,12 @@
+pub mod config;
 pub mod database;
+pub mod error;
 pub mod handlers;
-pub mod models;
\ No newline at end of file
+pub mod metrics;
+pub mod models;
+pub mod repository;
+pub mod request_id;
+pub mod routes;
+pub mod shutdown;
+pub mod state;
+pub mod telemetry;
@@ -1,25 +1,30 @@
 use axum::{
     http::{
-        header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
-        HeaderValue, Method,
+        header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH},
+        Method,
     },
-    routing::{delete, get, post, put},
-    Router,
+    middleware, Router,
 };
 use dotenv::dotenv;
 use std::env;
 use tower::ServiceBuilder;
-use tower_http::{
-    cors::CorsLayer,
-    trace::{DefaultMakeSpan, TraceLayer},
-};
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tower_http::{cors::CorsLayer, trace::TraceLayer};
 use utoipa::{
     OpenApi,
 };
 use utoipa_swagger_ui::SwaggerUi;
 
-use rust_api_rest::{database::create_connection_pool, handlers::*, models::*};
+use rust_api_rest::{
+    config::Settings,
+    metrics::{prometheus_handle, track_metrics},
+    models::*,
+    repository,
+    request_id::{request_id, X_REQUEST_ID},
+    routes,
+    shutdown::{serve_with_shutdown, shutdown_signal},
+    state::AppState,
+    telemetry::{init_tracing, RecordResponse, RequestSpan},
+};
 
 // Define la documentación OpenAPI/Swagger automáticamente
 #[derive(OpenApi)]
@@ -28,23 +33,40 @@ use rust_api_rest::{database::create_connection_pool, handlers::*, models::*};
         // Lista todos los endpoints que se documentarán
         rust_api_rest::handlers::get_alumnos,
         rust_api_rest::handlers::get_alumno,
+        rust_api_rest::handlers::get_duplicados,
         rust_api_rest::handlers::create_alumno,
         rust_api_rest::handlers::update_alumno,
+        rust_api_rest::handlers::patch_alumno,
         rust_api_rest::handlers::delete_alumno,
+        rust_api_rest::handlers::liveness,
+        rust_api_rest::handlers::readiness,
+        rust_api_rest::handlers::metrics_handler,
     ),
     components(
         schemas(
             // Lista todos los modelos para el schema JSON
             Alumno,
+            PartialAlumno,
             CreateAlumnoRequest,
             UpdateAlumnoRequest,
             AlumnoResponse,
+            PartialAlumnoResponse,
             AlumnosResponse,
-            ErrorResponse
+            PageLinks,
+            Similarity,
+            DuplicateCandidate,
+            DuplicatePair,
+            DuplicatesResponse,
+            ErrorResponse,
+            LivenessResponse,
+            ReadinessResponse,
+            DependencyCheck,
+            PoolStats
         )
     ),
     tags(
-        (name = "Alumnos", description = "API para gestión de alumnos")
+        (name = "Alumnos", description = "API para gestión de alumnos"),
+        (name = "Health", description = "Liveness y readiness para el orquestador")
     ),
     info(
         title = "API REST de Alumnos",
@@ -69,77 +91,90 @@ struct ApiDoc;
 async fn main() -> anyhow::Result<()> {
     dotenv().ok();
 
-    // Configurar sistema de logging estructurado
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "rust_api_rest=debug,tower_http=debug".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
-        .init();
-
-    // Obtener configuración desde variables de entorno con defaults
-    let database_url = env::var("DATABASE_URL")
-        .unwrap_or_else(|_| "mysql://root:@localhost:3306/alumnos_db".to_string());
-    let host = env::var("SERVER_HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-    let port = env::var("SERVER_PORT")
-        .unwrap_or_else(|_| "3000".to_string())
-        .parse::<u16>()
-        .unwrap_or(3000);
-
-    // Crear pool de conexiones a MySQL - compartido entre todos los handlers
+    // Cargar y validar la configuración; un valor inválido detiene el arranque
+    let settings = Settings::load()?;
+
+    // Configurar sistema de logging estructurado (RUST_LOG tiene prioridad)
+    let tracing_guard = init_tracing(&settings.log, &settings.otel)?;
+
+    // `rust-api-rest migrate` solo aplica las migraciones y termina
+    let migrate_only = env::args().nth(1).as_deref() == Some("migrate");
+
+    // Crear el repositorio según database.url - compartido entre todos los handlers
     tracing::info!("🔗 Conectando a la base de datos...");
-    let pool = create_connection_pool(&database_url).await?;
+    let migrate = migrate_only || settings.database.auto_migrate;
+    let alumnos = repository::connect(&settings.database, migrate).await?;
 
-    // Configurar CORS para permitir requests desde frontend
+    if migrate_only {
+        tracing::info!("✅ Migraciones aplicadas, saliendo");
+        return Ok(());
+    }
+
+    // Registrar el recorder de Prometheus antes de atender requests
+    prometheus_handle();
+
+    // Los handlers solo conocen el repositorio, no el motor de base de datos
+    let state = AppState::new(alumnos)
+        .with_pagination(settings.pagination.clone())
+        .with_concurrency(settings.concurrency.clone())
+        .with_duplicates(settings.duplicates.clone())
+        .with_ping_timeout(settings.database.ping_timeout());
+
+    // Configurar CORS para permitir requests desde los orígenes configurados
     let cors = CorsLayer::new()
-        .allow_origin("http://localhost:3000".parse::<HeaderValue>()?)
-        .allow_origin("http://127.0.0.1:3000".parse::<HeaderValue>()?)
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers([CONTENT_TYPE, AUTHORIZATION, ACCEPT]);
+        .allow_origin(settings.cors.origins())
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+        .allow_headers([CONTENT_TYPE, AUTHORIZATION, ACCEPT, IF_MATCH, IF_NONE_MATCH, X_REQUEST_ID])
+        .expose_headers([ETAG, X_REQUEST_ID]);
 
     // Definir rutas de la API con sus handlers correspondientes
-    let api_routes = Router::new()
-        .route("/alumnos", get(get_alumnos))        
-        .route("/alumnos", post(create_alumno))     
-        .route("/alumnos/{id}", get(get_alumno))    
-        .route("/alumnos/{id}", put(update_alumno)) 
-        .route("/alumnos/{id}", delete(delete_alumno)) 
-        .with_state(pool);
+    let api_routes = routes::api_routes(state.clone());
 
     // Crear aplicación principal con Swagger UI y middleware
     let app = Router::new()
         // Montar la UI de Swagger en /swagger-ui
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .nest("/api", api_routes) 
-        .route("/", get(health_check))      
-        .route("/health", get(health_check)) 
+        .merge(routes::health_routes(state.clone()))
+        .merge(routes::metrics_routes(state.clone()))
+        // Conteo y latencia por ruta; como route_layer conoce la plantilla de la ruta
+        .route_layer(middleware::from_fn(track_metrics))
         .layer(
             ServiceBuilder::new()
-                // Middleware de tracing para logging de requests
-                .layer(TraceLayer::new_for_http().make_span_with(DefaultMakeSpan::default().include_headers(true)))
+                // Acepta o genera X-Request-Id antes de crear el span de la request
+                .layer(middleware::from_fn(request_id))
+                // Middleware de tracing para logging de requests, con el request id en el span
+                // y sin los parámetros sensibles de `log.redact`
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(RequestSpan::new(&settings.log.redact))
+                        .on_response(RecordResponse),
+                )
                 .layer(cors), 
         );
 
     // Crear listener TCP y iniciar servidor
+    let (host, port) = (&settings.server.host, settings.server.port);
     let listener = tokio::net::TcpListener::bind(format!("{}:{}", host, port)).await?;
     
     tracing::info!("🚀 Servidor iniciado en http://{}:{}", host, port);
     tracing::info!("📚 Documentación Swagger disponible en http://{}:{}/swagger-ui", host, port);
-    tracing::info!("🏥 Health check disponible en http://{}:{}/health", host, port);
+    tracing::info!("📈 Métricas de Prometheus disponibles en http://{}:{}/metrics", host, port);
+    tracing::info!("🏥 Health checks disponibles en http://{}:{}/health/live y /health/ready", host, port);
 
-    // Iniciar servidor HTTP
-    axum::serve(listener, app).await?;
+    // Iniciar servidor HTTP; SIGTERM/SIGINT drenan las requests en curso antes de salir
+    serve_with_shutdown(
+        listener,
+        app,
+        state,
+        settings.server.shutdown_delay(),
+        settings.server.shutdown_timeout(),
+        shutdown_signal(),
+    )
+    .await?;
+
+    // Enviar las trazas pendientes; el exportador bloquea, así que sale del runtime
+    tokio::task::spawn_blocking(move || tracing_guard.shutdown()).await?;
 
     Ok(())
 }
-
-// Endpoint de health check para monitoreo
-async fn health_check() -> axum::response::Json<serde_json::Value> {
-    axum::response::Json(serde_json::json!({
-        "status": "ok",
-        "message": "API de Alumnos funcionando correctamente",
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "version": "1.0.0"
-    }))
-}
\ No newline at end of file
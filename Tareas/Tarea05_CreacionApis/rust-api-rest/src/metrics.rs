@@ -0,0 +1,74 @@
+use std::{sync::OnceLock, time::Instant};
+
+use ::metrics::{counter, histogram};
+use axum::{
+    extract::{MatchedPath, Request},
+    middleware::Next,
+    response::Response,
+};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+
+/// Contador de requests por método, ruta y código de estado
+pub const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+
+/// Histograma de latencia de requests en segundos
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+
+/// Histograma de la espera por una conexión del pool en segundos, registrado por los
+/// repositorios SQL en cada `acquire`
+pub const DB_POOL_ACQUIRE_WAIT_SECONDS: &str = "db_pool_acquire_wait_seconds";
+
+/// Límites de los buckets de latencia, de 5 ms a 10 s
+const LATENCY_BUCKETS: [f64; 12] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 7.5, 10.0,
+];
+
+/// Límites de los buckets de espera por una conexión, de 1 ms a 10 s
+const ACQUIRE_WAIT_BUCKETS: [f64; 12] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+static PROMETHEUS: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Instala (una sola vez) el recorder global de Prometheus y retorna su handle
+pub fn prometheus_handle() -> &'static PrometheusHandle {
+    PROMETHEUS.get_or_init(|| {
+        PrometheusBuilder::new()
+            .set_buckets_for_metric(
+                Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+                &LATENCY_BUCKETS,
+            )
+            .expect("los buckets de latencia no están vacíos")
+            .set_buckets_for_metric(
+                Matcher::Full(DB_POOL_ACQUIRE_WAIT_SECONDS.to_string()),
+                &ACQUIRE_WAIT_BUCKETS,
+            )
+            .expect("los buckets de espera no están vacíos")
+            .install_recorder()
+            .expect("no hay otro recorder de métricas instalado")
+    })
+}
+
+/// Middleware que registra cantidad y latencia de cada request.
+/// Se monta con `route_layer` para que la ruta sea la plantilla (`/api/alumnos/{id}`)
+/// y no la URL concreta, lo que mantiene acotadas las series.
+pub async fn track_metrics(request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| "unmatched".to_string(), |path| path.as_str().to_string());
+
+    let response = next.run(request).await;
+
+    let labels = [
+        ("method", method),
+        ("path", path),
+        ("status", response.status().as_u16().to_string()),
+    ];
+    counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels).record(started.elapsed().as_secs_f64());
+
+    response
+}
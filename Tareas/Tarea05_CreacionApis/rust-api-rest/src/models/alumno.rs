@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use utoipa::ToSchema;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 // Modelo principal que representa un alumno en la base de datos
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
@@ -18,6 +18,62 @@ pub struct Alumno {
     pub activo: bool,
     pub fecha_registro: DateTime<Utc>,
     pub fecha_actualizacion: DateTime<Utc>,
+    // Aumenta con cada cambio; se envía como ETag para detectar ediciones concurrentes
+    pub version: i32,
+}
+
+// Alumno con solo los campos pedidos en ?fields=; las columnas que no se seleccionaron
+// quedan en None (#[sqlx(default)]) y se omiten al serializar
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow, ToSchema)]
+#[sqlx(default)]
+pub struct PartialAlumno {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nombre: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apellido: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edad: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub carrera: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semestre: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub promedio: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activo: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fecha_registro: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fecha_actualizacion: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<i32>,
+    // Relevancia del alumno para `search` (mayor es más relevante); solo al buscar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+impl From<Alumno> for PartialAlumno {
+    fn from(alumno: Alumno) -> Self {
+        Self {
+            id: Some(alumno.id),
+            nombre: Some(alumno.nombre),
+            apellido: Some(alumno.apellido),
+            email: Some(alumno.email),
+            edad: Some(alumno.edad),
+            carrera: Some(alumno.carrera),
+            semestre: Some(alumno.semestre),
+            promedio: Some(alumno.promedio),
+            activo: Some(alumno.activo),
+            fecha_registro: Some(alumno.fecha_registro),
+            fecha_actualizacion: Some(alumno.fecha_actualizacion),
+            version: Some(alumno.version),
+            score: None,
+        }
+    }
 }
 
 // DTO para crear nuevos alumnos con validaciones
@@ -77,12 +133,53 @@ pub struct UpdateAlumnoRequest {
     pub activo: Option<bool>,
 }
 
+// Un PUT reemplaza todos los campos de CreateAlumnoRequest; `activo` conserva su valor
+impl From<&CreateAlumnoRequest> for UpdateAlumnoRequest {
+    fn from(alumno: &CreateAlumnoRequest) -> Self {
+        Self {
+            nombre: Some(alumno.nombre.clone()),
+            apellido: Some(alumno.apellido.clone()),
+            email: Some(alumno.email.clone()),
+            edad: Some(alumno.edad),
+            carrera: Some(alumno.carrera.clone()),
+            semestre: Some(alumno.semestre),
+            promedio: Some(alumno.promedio.unwrap_or(0.0)),
+            activo: None,
+        }
+    }
+}
+
+impl UpdateAlumnoRequest {
+    /// Indica si el request no trae ningún campo para actualizar
+    pub fn is_empty(&self) -> bool {
+        self.nombre.is_none()
+            && self.apellido.is_none()
+            && self.email.is_none()
+            && self.edad.is_none()
+            && self.carrera.is_none()
+            && self.semestre.is_none()
+            && self.promedio.is_none()
+            && self.activo.is_none()
+    }
+}
+
 // Respuesta para operaciones que retornan un solo alumno
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AlumnoResponse {
     pub success: bool,          
     pub message: String,        
     pub data: Option<Alumno>,   
+    // Alumnos parecidos al creado (sin duplicates.block_on_create); se omite si no hay
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicados: Option<Vec<DuplicateCandidate>>,
+}
+
+// Respuesta de GET /api/alumnos/{id}, con los campos pedidos en ?fields=
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PartialAlumnoResponse {
+    pub success: bool,
+    pub message: String,
+    pub data: Option<PartialAlumno>,
 }
 
 // Respuesta para operaciones que retornan múltiples alumnos
@@ -90,8 +187,89 @@ pub struct AlumnoResponse {
 pub struct AlumnosResponse {
     pub success: bool,
     pub message: String,
-    pub data: Vec<Alumno>,      
+    pub data: Vec<PartialAlumno>, // Con los campos pedidos en ?fields= (todos por defecto)
     pub total: i64,             
+    // Número de página; se omite en el modo por cursor
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<i64>,
+    pub limit: i64,
+    pub total_pages: i64,
+    pub has_next: bool,
+    pub has_prev: bool,
+    // Cursores opacos para ?after= y ?before=; se omiten si no hay más alumnos en esa dirección
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
+    pub links: PageLinks,
+}
+
+// Enlaces de navegación del listado; también se envían en la cabecera `Link` (RFC 8288)
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PageLinks {
+    #[serde(rename = "self")]
+    pub current: String,
+    pub first: String,
+    pub last: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev: Option<String>,
+}
+
+impl PageLinks {
+    /// Valor de la cabecera `Link`: `<uri>; rel="next", ...`
+    pub fn header(&self) -> String {
+        [
+            ("self", Some(&self.current)),
+            ("first", Some(&self.first)),
+            ("prev", self.prev.as_ref()),
+            ("next", self.next.as_ref()),
+            ("last", Some(&self.last)),
+        ]
+        .into_iter()
+        .filter_map(|(rel, uri)| uri.map(|uri| format!("<{uri}>; rel=\"{rel}\"")))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+// Similitud entre dos alumnos, de 0.0 (distintos) a 1.0 (iguales) en cada criterio;
+// `score` es el promedio ponderado: nombre 60%, parte local del email 30% y edad 10%
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Similarity {
+    pub score: f64,
+    pub nombre: f64,
+    pub email: f64,
+    pub edad: f64,
+}
+
+// Alumno registrado que podría ser la misma persona que el que se quiere crear; solo
+// se identifica por ID y nombre para no exponer los datos de contacto de otro alumno
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DuplicateCandidate {
+    pub id: i32,
+    pub nombre: String,
+    pub apellido: String,
+    pub similitud: Similarity,
+}
+
+// Par de alumnos registrados que podrían ser la misma persona
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DuplicatePair {
+    pub alumno: Alumno,
+    pub duplicado: Alumno,
+    pub similitud: Similarity,
+}
+
+// Respuesta de GET /api/alumnos/duplicados, con los pares más parecidos primero
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DuplicatesResponse {
+    pub success: bool,
+    pub message: String,
+    pub data: Vec<DuplicatePair>, // Hasta `limit` pares
+    pub total: i64,               // Pares encontrados, aunque no entren en `data`
+    pub limit: i64,
 }
 
 // Respuesta estándar para errores
@@ -100,14 +278,103 @@ pub struct ErrorResponse {
     pub success: bool,
     pub message: String,                  
     pub errors: Option<Vec<String>>,      
+    // Alumnos parecidos que bloquearon la creación (409, con duplicates.block_on_create);
+    // se omite en los demás errores
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicados: Option<Vec<DuplicateCandidate>>,
+    // Mismo valor que la cabecera X-Request-Id, para ubicar la request en los logs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 // Query parameters para paginación y filtros
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+#[validate(
+    schema(function = "rango_edad"),
+    schema(function = "rango_semestre"),
+    schema(function = "rango_promedio"),
+    schema(function = "rango_registro")
+)]
 pub struct PaginationQuery {
     pub page: Option<i32>,      // Número de página (default: 1)
     pub limit: Option<i32>,     // Elementos por página (default: 10)
-    pub search: Option<String>, // Búsqueda por nombre/apellido
-    pub carrera: Option<String>,// Filtro por carrera
+    pub after: Option<String>,  // Cursor: alumnos posteriores (no se combina con page)
+    pub before: Option<String>, // Cursor: alumnos anteriores (no se combina con page)
+    pub sort: Option<String>,   // Orden: columnas separadas por coma, `-` para descendente
+    pub fields: Option<String>, // Campos a devolver, separados por coma (default: todos)
+    pub search: Option<String>, // Búsqueda de texto completo (nombre, apellido, email, carrera)
+    pub carrera: Option<String>,// Filtro por carrera (varias separadas por coma)
     pub activo: Option<bool>,   // Filtro por estado activo
+    pub edad_min: Option<i32>,
+    pub edad_max: Option<i32>,
+    pub semestre_min: Option<i32>,
+    pub semestre_max: Option<i32>,
+    pub promedio_min: Option<f64>,
+    pub promedio_max: Option<f64>,
+    pub registrado_desde: Option<DateTime<Utc>>, // fecha_registro >= (RFC 3339)
+    pub registrado_hasta: Option<DateTime<Utc>>, // fecha_registro <= (RFC 3339)
+    pub email_dominio: Option<String>, // Dominio del email (varios separados por coma)
+}
+
+// Query parameters del detalle de un alumno
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FieldsQuery {
+    pub fields: Option<String>, // Campos a devolver, separados por coma (default: todos)
+}
+
+// Query parameters de POST /api/alumnos
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CreateAlumnoQuery {
+    pub force: Option<bool>, // No buscar posibles duplicados antes de crear (default: false)
+}
+
+// Query parameters de GET /api/alumnos/duplicados
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct DuplicatesQuery {
+    // Similitud mínima de un par (default: 0.8)
+    #[validate(range(min = 0.0, max = 1.0, message = "umbral debe estar entre 0.0 y 1.0"))]
+    pub umbral: Option<f64>,
+    pub limit: Option<i32>, // Pares a devolver (default y máximo: los de la paginación)
+}
+
+// Los rangos de los filtros no pueden estar invertidos
+fn rango<T: PartialOrd>(
+    min: Option<T>,
+    max: Option<T>,
+    message: &'static str,
+) -> Result<(), ValidationError> {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => {
+            Err(ValidationError::new("rango").with_message(message.into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn rango_edad(query: &PaginationQuery) -> Result<(), ValidationError> {
+    rango(query.edad_min, query.edad_max, "edad_min no puede ser mayor que edad_max")
+}
+
+fn rango_semestre(query: &PaginationQuery) -> Result<(), ValidationError> {
+    rango(
+        query.semestre_min,
+        query.semestre_max,
+        "semestre_min no puede ser mayor que semestre_max",
+    )
+}
+
+fn rango_promedio(query: &PaginationQuery) -> Result<(), ValidationError> {
+    rango(
+        query.promedio_min,
+        query.promedio_max,
+        "promedio_min no puede ser mayor que promedio_max",
+    )
+}
+
+fn rango_registro(query: &PaginationQuery) -> Result<(), ValidationError> {
+    rango(
+        query.registrado_desde,
+        query.registrado_hasta,
+        "registrado_desde no puede ser posterior a registrado_hasta",
+    )
 }
\ No newline at end of file
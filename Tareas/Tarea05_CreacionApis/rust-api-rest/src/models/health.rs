@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// Respuesta de liveness: el proceso está vivo y atiende requests
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LivenessResponse {
+    pub status: String,
+    pub message: String,
+    pub timestamp: String,
+    pub version: String,
+    pub uptime_secs: u64,
+}
+
+// Respuesta de readiness con el estado de cada dependencia
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessResponse {
+    pub status: String, // "ok", "unavailable" o "shutting_down"
+    pub timestamp: String,
+    pub uptime_secs: u64,
+    pub database: DependencyCheck,
+    pub pool: Option<PoolStats>, // None en el almacenamiento en memoria
+    pub migration_version: Option<i64>, // Última migración aplicada
+}
+
+// Resultado de verificar una dependencia
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DependencyCheck {
+    pub healthy: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+// Estadísticas del pool de conexiones
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PoolStats {
+    pub size: u32,            // Conexiones abiertas (en uso + inactivas)
+    pub idle: u32,            // Conexiones inactivas disponibles
+    pub max_connections: u32, // Límite configurado del pool
+}
@@ -1,2 +1,4 @@
 pub mod alumno;
-pub use alumno::*;
\ No newline at end of file
+pub mod health;
+pub use alumno::*;
+pub use health::*;
@@ -0,0 +1,218 @@
+use std::{
+    collections::BTreeMap,
+    sync::{PoisonError, RwLock},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, SubsecRound, Utc};
+
+use super::AlumnoRepository;
+use crate::{
+    database::{AlumnoFilter, FieldSet, PageStart, Sort, check_version},
+    error::AppError,
+    models::*,
+};
+
+/// Repositorio de alumnos en memoria, para desarrollo y pruebas sin MySQL.
+/// Respeta las mismas reglas que la tabla `alumnos`: email único, ID autoincremental
+/// que no se reutiliza y fechas de registro/actualización asignadas por el almacén.
+#[derive(Debug, Default)]
+pub struct InMemoryAlumnoRepository {
+    store: RwLock<Store>,
+}
+
+#[derive(Debug, Default)]
+struct Store {
+    last_id: i32,
+    alumnos: BTreeMap<i32, Alumno>,
+}
+
+impl Store {
+    // El email es único sin distinguir mayúsculas, como en la collation de MySQL
+    fn email_taken(&self, email: &str, except_id: Option<i32>) -> bool {
+        self.alumnos.values().any(|alumno| {
+            Some(alumno.id) != except_id && alumno.email.to_lowercase() == email.to_lowercase()
+        })
+    }
+
+    fn filtered(&self, filter: &AlumnoFilter) -> Vec<&Alumno> {
+        self.alumnos
+            .values()
+            .filter(|alumno| filter.matches(alumno))
+            .collect()
+    }
+}
+
+impl InMemoryAlumnoRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// Las columnas DATETIME de MySQL no guardan fracciones de segundo
+fn now() -> DateTime<Utc> {
+    Utc::now().trunc_subsecs(0)
+}
+
+fn email_conflict() -> AppError {
+    AppError::Conflict("El email ya existe".to_string())
+}
+
+#[async_trait]
+impl AlumnoRepository for InMemoryAlumnoRepository {
+    async fn list(
+        &self,
+        filter: &AlumnoFilter,
+        _fields: &FieldSet,
+        sort: &Sort,
+        start: &PageStart,
+        limit: i64,
+    ) -> Result<Vec<PartialAlumno>, AppError> {
+        let store = self.store.read().unwrap_or_else(PoisonError::into_inner);
+        // Mismo orden que el listado SQL; los alumnos se devuelven completos
+        let mut alumnos: Vec<PartialAlumno> = store
+            .filtered(filter)
+            .into_iter()
+            .map(|alumno| PartialAlumno {
+                score: filter.score(alumno),
+                ..alumno.clone().into()
+            })
+            .collect();
+        alumnos.sort_by(|a, b| sort.compare_alumnos(a, b));
+        let limit = limit.max(0) as usize;
+
+        let page: Vec<PartialAlumno> = match start {
+            PageStart::Offset(offset) => alumnos
+                .into_iter()
+                .skip((*offset).max(0) as usize)
+                .take(limit)
+                .collect(),
+            PageStart::After(cursor) => alumnos
+                .into_iter()
+                .filter(|alumno| cursor.precedes(alumno, sort))
+                .take(limit)
+                .collect(),
+            // Los `limit` alumnos inmediatamente anteriores al cursor
+            PageStart::Before(cursor) => {
+                let before: Vec<PartialAlumno> = alumnos
+                    .into_iter()
+                    .take_while(|alumno| cursor.follows(alumno, sort))
+                    .collect();
+                before[before.len().saturating_sub(limit)..].to_vec()
+            }
+        };
+
+        Ok(page)
+    }
+
+    async fn count(&self, filter: &AlumnoFilter) -> Result<i64, AppError> {
+        let store = self.store.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(store.filtered(filter).len() as i64)
+    }
+
+    async fn batch(&self, after: i32, limit: i64) -> Result<Vec<Alumno>, AppError> {
+        let store = self.store.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(store
+            .alumnos
+            .range(after.saturating_add(1)..)
+            .map(|(_, alumno)| alumno.clone())
+            .take(limit.max(0) as usize)
+            .collect())
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<Alumno>, AppError> {
+        let store = self.store.read().unwrap_or_else(PoisonError::into_inner);
+        Ok(store.alumnos.get(&id).cloned())
+    }
+
+    async fn create(&self, alumno: &CreateAlumnoRequest) -> Result<Alumno, AppError> {
+        let mut store = self.store.write().unwrap_or_else(PoisonError::into_inner);
+
+        if store.email_taken(&alumno.email, None) {
+            return Err(email_conflict());
+        }
+
+        store.last_id += 1;
+        let timestamp = now();
+        let alumno = Alumno {
+            id: store.last_id,
+            nombre: alumno.nombre.clone(),
+            apellido: alumno.apellido.clone(),
+            email: alumno.email.clone(),
+            edad: alumno.edad,
+            carrera: alumno.carrera.clone(),
+            semestre: alumno.semestre,
+            promedio: alumno.promedio.unwrap_or(0.0),
+            activo: true,
+            fecha_registro: timestamp,
+            fecha_actualizacion: timestamp,
+            version: 1,
+        };
+
+        store.alumnos.insert(alumno.id, alumno.clone());
+        Ok(alumno)
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        changes: &UpdateAlumnoRequest,
+        version: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError> {
+        let mut store = self.store.write().unwrap_or_else(PoisonError::into_inner);
+
+        let Some(current) = store.alumnos.get(&id) else {
+            return Ok(None);
+        };
+        check_version(current.version, version)?;
+        if let Some(email) = &changes.email
+            && store.email_taken(email, Some(id))
+        {
+            return Err(email_conflict());
+        }
+
+        let Some(alumno) = store.alumnos.get_mut(&id) else {
+            return Ok(None);
+        };
+
+        if let Some(nombre) = &changes.nombre {
+            alumno.nombre = nombre.clone();
+        }
+        if let Some(apellido) = &changes.apellido {
+            alumno.apellido = apellido.clone();
+        }
+        if let Some(email) = &changes.email {
+            alumno.email = email.clone();
+        }
+        if let Some(edad) = changes.edad {
+            alumno.edad = edad;
+        }
+        if let Some(carrera) = &changes.carrera {
+            alumno.carrera = carrera.clone();
+        }
+        if let Some(semestre) = changes.semestre {
+            alumno.semestre = semestre;
+        }
+        if let Some(promedio) = changes.promedio {
+            alumno.promedio = promedio;
+        }
+        if let Some(activo) = changes.activo {
+            alumno.activo = activo;
+        }
+        if !changes.is_empty() {
+            alumno.fecha_actualizacion = now();
+            alumno.version += 1;
+        }
+
+        Ok(Some(alumno.clone()))
+    }
+
+    async fn delete(&self, id: i32, version: Option<i32>) -> Result<bool, AppError> {
+        let mut store = self.store.write().unwrap_or_else(PoisonError::into_inner);
+        let Some(current) = store.alumnos.get(&id) else {
+            return Ok(false);
+        };
+        check_version(current.version, version)?;
+        Ok(store.alumnos.remove(&id).is_some())
+    }
+}
@@ -0,0 +1,172 @@
+pub mod memory;
+pub mod mysql;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+mod sql;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub use memory::*;
+pub use mysql::*;
+#[cfg(feature = "postgres")]
+pub use postgres::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite::*;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+    config::DatabaseSettings,
+    database::{AlumnoFilter, FieldSet, PageStart, SearchQuery, Sort, create_connection_pool},
+    error::AppError,
+    models::*,
+};
+
+/// Esquema de `DATABASE_URL` que selecciona el almacén en memoria
+pub const MEMORY_URL_SCHEME: &str = "memory:";
+
+/// Esquema de `DATABASE_URL` que selecciona SQLite (requiere la feature `sqlite`)
+pub const SQLITE_URL_SCHEME: &str = "sqlite:";
+
+/// Esquemas de `DATABASE_URL` que seleccionan PostgreSQL (requiere la feature `postgres`)
+pub const POSTGRES_URL_SCHEMES: [&str; 2] = ["postgres:", "postgresql:"];
+
+/// Alumnos que lee por vez el `matching` por defecto
+const MATCHING_BATCH: i64 = 500;
+
+/// Operaciones de persistencia de alumnos, independientes del motor de base de datos
+#[async_trait]
+pub trait AlumnoRepository: Send + Sync {
+    /// Obtiene hasta `limit` alumnos que cumplen el filtro desde `start`, en el orden
+    /// `sort` (con el ID como desempate). Cada alumno trae al menos los campos de `fields`
+    /// y las columnas del orden.
+    async fn list(
+        &self,
+        filter: &AlumnoFilter,
+        fields: &FieldSet,
+        sort: &Sort,
+        start: &PageStart,
+        limit: i64,
+    ) -> Result<Vec<PartialAlumno>, AppError>;
+
+    /// Cuenta los alumnos que cumplen el filtro
+    async fn count(&self, filter: &AlumnoFilter) -> Result<i64, AppError>;
+
+    /// Hasta `limit` alumnos completos con ID mayor que `after`, ordenados por ID, para
+    /// recorrer la tabla por lotes (como el reporte de duplicados)
+    async fn batch(&self, after: i32, limit: i64) -> Result<Vec<Alumno>, AppError>;
+
+    /// Alumnos completos que coinciden con la búsqueda de texto completo, ordenados por
+    /// ID (p. ej. los candidatos a duplicado de `Identity::candidates_search`). Los motores
+    /// SQL usan su índice de búsqueda; por defecto se filtran los alumnos lote a lote.
+    async fn matching(&self, search: &SearchQuery) -> Result<Vec<Alumno>, AppError> {
+        let mut alumnos = Vec::new();
+        let mut after = 0;
+        loop {
+            let batch = self.batch(after, MATCHING_BATCH).await?;
+            let Some(last) = batch.last() else {
+                return Ok(alumnos);
+            };
+            after = last.id;
+            alumnos.extend(
+                batch
+                    .into_iter()
+                    .filter(|alumno| search.score(alumno).is_some()),
+            );
+        }
+    }
+
+    /// Busca un alumno por ID
+    async fn get(&self, id: i32) -> Result<Option<Alumno>, AppError>;
+
+    /// Busca un alumno por ID con al menos los campos de `fields` y la versión (para el
+    /// ETag). Los motores SQL leen únicamente esas columnas; por defecto se retorna el
+    /// alumno completo.
+    async fn get_fields(
+        &self,
+        id: i32,
+        _fields: &FieldSet,
+    ) -> Result<Option<PartialAlumno>, AppError> {
+        Ok(self.get(id).await?.map(PartialAlumno::from))
+    }
+
+    /// Inserta un alumno y lo retorna con su ID y fechas asignadas
+    async fn create(&self, alumno: &CreateAlumnoRequest) -> Result<Alumno, AppError>;
+
+    /// Actualiza solo los campos presentes e incrementa la versión; retorna `None` si el
+    /// alumno no existe. Con `version`, un alumno que ya no está en esa versión es un 412.
+    async fn update(
+        &self,
+        id: i32,
+        changes: &UpdateAlumnoRequest,
+        version: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError>;
+
+    /// Elimina un alumno; retorna `false` si no existía. Con `version`, un alumno que ya
+    /// no está en esa versión es un 412.
+    async fn delete(&self, id: i32, version: Option<i32>) -> Result<bool, AppError>;
+
+    /// Verifica que el almacén responda (`SELECT 1` en los motores SQL)
+    async fn ping(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    /// Estadísticas del pool de conexiones; `None` si no hay pool
+    fn pool_stats(&self) -> Option<PoolStats> {
+        None
+    }
+
+    /// Versión de la última migración aplicada; `None` si no hay migraciones
+    async fn schema_version(&self) -> Result<Option<i64>, AppError> {
+        Ok(None)
+    }
+
+    /// Cierra las conexiones al apagar; espera a que se devuelvan las que están en uso
+    async fn close(&self) {}
+}
+
+/// Crea el repositorio según el esquema de `database.url`: `memory://` usa el almacén
+/// en memoria, `sqlite:` usa SQLite, `postgres://` usa PostgreSQL y cualquier otra URL
+/// se conecta a MySQL. Con `migrate` se aplican las migraciones pendientes del motor.
+pub async fn connect(
+    settings: &DatabaseSettings,
+    migrate: bool,
+) -> anyhow::Result<Arc<dyn AlumnoRepository>> {
+    let database_url = settings.url.as_str();
+
+    if database_url.starts_with(MEMORY_URL_SCHEME) {
+        tracing::warn!("⚠️ Usando almacenamiento en memoria: los datos se pierden al reiniciar");
+        return Ok(Arc::new(InMemoryAlumnoRepository::new()));
+    }
+
+    if database_url.starts_with(SQLITE_URL_SCHEME) {
+        #[cfg(feature = "sqlite")]
+        {
+            let pool = crate::database::create_sqlite_pool(settings, migrate).await?;
+            return Ok(Arc::new(SqliteAlumnoRepository::new(pool)));
+        }
+
+        #[cfg(not(feature = "sqlite"))]
+        anyhow::bail!("DATABASE_URL usa SQLite pero el binario se compiló sin la feature `sqlite`");
+    }
+
+    if POSTGRES_URL_SCHEMES
+        .iter()
+        .any(|scheme| database_url.starts_with(scheme))
+    {
+        #[cfg(feature = "postgres")]
+        {
+            let pool = crate::database::create_postgres_pool(settings, migrate).await?;
+            return Ok(Arc::new(PgAlumnoRepository::new(pool)));
+        }
+
+        #[cfg(not(feature = "postgres"))]
+        anyhow::bail!(
+            "DATABASE_URL usa PostgreSQL pero el binario se compiló sin la feature `postgres`"
+        );
+    }
+
+    let pool = create_connection_pool(settings, migrate).await?;
+    Ok(Arc::new(MySqlAlumnoRepository::new(pool)))
+}
@@ -0,0 +1,92 @@
+use sqlx::{Connection, MySql};
+
+use super::sql::sql_repository;
+use crate::{
+    database::{DbPool, check_version, update_query},
+    error::AppError,
+    models::*,
+};
+
+sql_repository! {
+    /// Repositorio de alumnos sobre MySQL
+    MySqlAlumnoRepository(DbPool, MySql, id = "?");
+
+    async fn create(&self, alumno: &CreateAlumnoRequest) -> Result<Alumno, AppError> {
+        // Prepared statement para prevenir SQL injection
+        let query = r#"
+            INSERT INTO alumnos (nombre, apellido, email, edad, carrera, semestre, promedio)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        // El INSERT y la lectura del registro van en una transacción: la lectura ve el alumno
+        // tal como se insertó aunque otra request lo modifique o elimine enseguida
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin().await?;
+
+        // Usar valor por defecto para promedio si no se proporciona
+        let result = sqlx::query(query)
+            .bind(&alumno.nombre)
+            .bind(&alumno.apellido)
+            .bind(&alumno.email)
+            .bind(alumno.edad)
+            .bind(&alumno.carrera)
+            .bind(alumno.semestre)
+            .bind(alumno.promedio.unwrap_or(0.0))
+            .execute(&mut *tx)
+            .await?;
+
+        // Fetch del alumno recién creado para retornarlo
+        let alumno = sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ?")
+            .bind(result.last_insert_id() as i32)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(alumno)
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        changes: &UpdateAlumnoRequest,
+        version: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError> {
+        // Lectura, UPDATE y relectura en una transacción; FOR UPDATE bloquea la fila hasta el
+        // commit, así que otra request no puede modificarla ni eliminarla entre los pasos.
+        // Al retornar antes del commit la transacción se descarta con un rollback.
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin().await?;
+
+        // Verificar que el alumno existe (y su versión) antes de actualizar
+        let Some(existing) =
+            sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ? FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+        else {
+            return Ok(None);
+        };
+        check_version(existing.version, version)?;
+
+        // Sin cambios no hay nada que ejecutar
+        if changes.is_empty() {
+            return Ok(Some(existing));
+        }
+
+        // Construir query UPDATE dinámicamente - solo campos proporcionados; con la fila
+        // bloqueada la versión ya está verificada
+        let mut query_builder = update_query::<MySql>(id, changes);
+
+        // Un email duplicado se convierte en 409 a través de AppError
+        query_builder.build().execute(&mut *tx).await?;
+
+        // Retornar el alumno actualizado
+        let alumno = sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ?")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(alumno))
+    }
+}
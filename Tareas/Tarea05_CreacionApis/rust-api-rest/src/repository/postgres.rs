@@ -0,0 +1,75 @@
+use sqlx::{Connection, Postgres};
+
+use super::sql::sql_repository;
+use crate::{
+    database::{PgDbPool, check_version, update_query},
+    error::AppError,
+    models::*,
+};
+
+sql_repository! {
+    /// Repositorio de alumnos sobre PostgreSQL (feature `postgres`)
+    PgAlumnoRepository(PgDbPool, Postgres, id = "$1");
+
+    async fn create(&self, alumno: &CreateAlumnoRequest) -> Result<Alumno, AppError> {
+        // RETURNING reemplaza a last_insert_id() de MySQL
+        let query = r#"
+            INSERT INTO alumnos (nombre, apellido, email, edad, carrera, semestre, promedio)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+        "#;
+
+        let alumno = sqlx::query_as::<_, Alumno>(query)
+            .bind(&alumno.nombre)
+            .bind(&alumno.apellido)
+            .bind(&alumno.email)
+            .bind(alumno.edad)
+            .bind(&alumno.carrera)
+            .bind(alumno.semestre)
+            .bind(alumno.promedio.unwrap_or(0.0))
+            .fetch_one(&mut *self.acquire().await?)
+            .await?;
+
+        Ok(alumno)
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        changes: &UpdateAlumnoRequest,
+        version: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError> {
+        // Lectura y UPDATE en una transacción; FOR UPDATE bloquea la fila hasta el commit,
+        // así que otra request no puede modificarla ni eliminarla entre los pasos. Al
+        // retornar antes del commit la transacción se descarta con un rollback.
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin().await?;
+
+        let Some(existing) =
+            sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?
+        else {
+            return Ok(None);
+        };
+        check_version(existing.version, version)?;
+
+        // Sin cambios solo se retorna el registro actual
+        if changes.is_empty() {
+            return Ok(Some(existing));
+        }
+
+        // Con la fila bloqueada la versión ya está verificada
+        let mut query_builder = update_query::<Postgres>(id, changes);
+        query_builder.push(" RETURNING *");
+
+        let alumno = query_builder
+            .build_query_as::<Alumno>()
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(alumno))
+    }
+}
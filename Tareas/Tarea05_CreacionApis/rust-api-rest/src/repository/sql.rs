@@ -0,0 +1,199 @@
+/// Define un repositorio de alumnos sobre un pool de sqlx con las operaciones comunes a
+/// los motores SQL. Cada motor indica su pool, su tipo `sqlx::Database` y el placeholder
+/// del ID, y aporta solo lo que depende del dialecto (`create` y `update`). Las consultas
+/// toman la conexión con `acquire`, que registra la espera en el histograma
+/// `db_pool_acquire_wait_seconds`.
+///
+/// ```ignore
+/// sql_repository! {
+///     /// Repositorio de alumnos sobre SQLite
+///     SqliteAlumnoRepository(SqliteDbPool, Sqlite, id = "?");
+///
+///     async fn create(&self, alumno: &CreateAlumnoRequest) -> Result<Alumno, AppError> { ... }
+///     async fn update(...) -> Result<Option<Alumno>, AppError> { ... }
+/// }
+/// ```
+macro_rules! sql_repository {
+    (
+        $(#[$meta:meta])*
+        $name:ident($pool:ty, $db:ty, id = $id:literal);
+
+        $($dialect:tt)*
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $name {
+            pool: $pool,
+        }
+
+        impl $name {
+            pub fn new(pool: $pool) -> Self {
+                Self { pool }
+            }
+
+            /// Obtiene una conexión del pool y registra cuánto se esperó por ella, también
+            /// si la espera termina en error (p. ej. al agotarse `acquire_timeout`)
+            async fn acquire(
+                &self,
+            ) -> Result<sqlx::pool::PoolConnection<$db>, $crate::error::AppError> {
+                let started = std::time::Instant::now();
+                let connection = self.pool.acquire().await;
+                ::metrics::histogram!($crate::metrics::DB_POOL_ACQUIRE_WAIT_SECONDS)
+                    .record(started.elapsed().as_secs_f64());
+
+                Ok(connection?)
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl $crate::repository::AlumnoRepository for $name {
+            $($dialect)*
+
+            async fn list(
+                &self,
+                filter: &$crate::database::AlumnoFilter,
+                fields: &$crate::database::FieldSet,
+                sort: &$crate::database::Sort,
+                start: &$crate::database::PageStart,
+                limit: i64,
+            ) -> Result<Vec<$crate::models::PartialAlumno>, $crate::error::AppError> {
+                let alumnos = filter
+                    .select_query::<$db>(fields, sort, start, limit)
+                    .build_query_as::<$crate::models::PartialAlumno>()
+                    .fetch_all(&mut *self.acquire().await?)
+                    .await?;
+
+                Ok(alumnos)
+            }
+
+            async fn count(
+                &self,
+                filter: &$crate::database::AlumnoFilter,
+            ) -> Result<i64, $crate::error::AppError> {
+                let total: (i64,) = filter
+                    .count_query::<$db>()
+                    .build_query_as()
+                    .fetch_one(&mut *self.acquire().await?)
+                    .await?;
+
+                Ok(total.0)
+            }
+
+            async fn batch(
+                &self,
+                after: i32,
+                limit: i64,
+            ) -> Result<Vec<$crate::models::Alumno>, $crate::error::AppError> {
+                let mut builder =
+                    sqlx::QueryBuilder::<$db>::new("SELECT * FROM alumnos WHERE id > ");
+                builder.push_bind(after);
+                builder.push(" ORDER BY id LIMIT ");
+                builder.push_bind(limit);
+
+                let alumnos = builder
+                    .build_query_as::<$crate::models::Alumno>()
+                    .fetch_all(&mut *self.acquire().await?)
+                    .await?;
+
+                Ok(alumnos)
+            }
+
+            async fn matching(
+                &self,
+                search: &$crate::database::SearchQuery,
+            ) -> Result<Vec<$crate::models::Alumno>, $crate::error::AppError> {
+                let mut builder = sqlx::QueryBuilder::<$db>::new("SELECT * FROM ");
+                search.push_source(&mut builder);
+                builder.push(" ORDER BY id");
+
+                let alumnos = builder
+                    .build_query_as::<$crate::models::Alumno>()
+                    .fetch_all(&mut *self.acquire().await?)
+                    .await?;
+
+                Ok(alumnos)
+            }
+
+            async fn get(
+                &self,
+                id: i32,
+            ) -> Result<Option<$crate::models::Alumno>, $crate::error::AppError> {
+                let alumno = sqlx::query_as::<_, $crate::models::Alumno>(concat!(
+                    "SELECT * FROM alumnos WHERE id = ",
+                    $id
+                ))
+                .bind(id)
+                .fetch_optional(&mut *self.acquire().await?)
+                .await?;
+
+                Ok(alumno)
+            }
+
+            async fn get_fields(
+                &self,
+                id: i32,
+                fields: &$crate::database::FieldSet,
+            ) -> Result<Option<$crate::models::PartialAlumno>, $crate::error::AppError> {
+                let query = format!(
+                    "SELECT {} FROM alumnos WHERE id = {}",
+                    fields.select_list(&[$crate::database::AlumnoColumn::Version]),
+                    $id
+                );
+                let alumno = sqlx::query_as::<_, $crate::models::PartialAlumno>(&query)
+                    .bind(id)
+                    .fetch_optional(&mut *self.acquire().await?)
+                    .await?;
+
+                Ok(alumno)
+            }
+
+            async fn delete(
+                &self,
+                id: i32,
+                version: Option<i32>,
+            ) -> Result<bool, $crate::error::AppError> {
+                let result = $crate::database::delete_query::<$db>(id, version)
+                    .build()
+                    .execute(&mut *self.acquire().await?)
+                    .await?;
+                if result.rows_affected() == 0 {
+                    // Sin filas el alumno no existe o ya no está en `version`
+                    return match self.get(id).await? {
+                        Some(_) if version.is_some() => Err($crate::database::version_mismatch()),
+                        _ => Ok(false),
+                    };
+                }
+
+                Ok(true)
+            }
+
+            async fn ping(&self) -> Result<(), $crate::error::AppError> {
+                sqlx::query("SELECT 1").execute(&mut *self.acquire().await?).await?;
+                Ok(())
+            }
+
+            fn pool_stats(&self) -> Option<$crate::models::PoolStats> {
+                Some($crate::models::PoolStats {
+                    size: self.pool.size(),
+                    idle: self.pool.num_idle() as u32,
+                    max_connections: self.pool.options().get_max_connections(),
+                })
+            }
+
+            async fn schema_version(&self) -> Result<Option<i64>, $crate::error::AppError> {
+                let version: Option<i64> =
+                    sqlx::query_scalar($crate::database::SCHEMA_VERSION_QUERY)
+                        .fetch_one(&mut *self.acquire().await?)
+                        .await?;
+
+                Ok(version)
+            }
+
+            async fn close(&self) {
+                self.pool.close().await;
+            }
+        }
+    };
+}
+
+pub(crate) use sql_repository;
@@ -0,0 +1,78 @@
+use sqlx::{Connection, Sqlite};
+
+use super::sql::sql_repository;
+use crate::{
+    database::{SqliteDbPool, check_version, update_query},
+    error::AppError,
+    models::*,
+};
+
+sql_repository! {
+    /// Repositorio de alumnos sobre SQLite (feature `sqlite`)
+    SqliteAlumnoRepository(SqliteDbPool, Sqlite, id = "?");
+
+    async fn create(&self, alumno: &CreateAlumnoRequest) -> Result<Alumno, AppError> {
+        // RETURNING evita una segunda consulta para obtener el registro creado
+        let query = r#"
+            INSERT INTO alumnos (nombre, apellido, email, edad, carrera, semestre, promedio)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            RETURNING *
+        "#;
+
+        let alumno = sqlx::query_as::<_, Alumno>(query)
+            .bind(&alumno.nombre)
+            .bind(&alumno.apellido)
+            .bind(&alumno.email)
+            .bind(alumno.edad)
+            .bind(&alumno.carrera)
+            .bind(alumno.semestre)
+            .bind(alumno.promedio.unwrap_or(0.0))
+            .fetch_one(&mut *self.acquire().await?)
+            .await?;
+
+        Ok(alumno)
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        changes: &UpdateAlumnoRequest,
+        version: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError> {
+        // Lectura, UPDATE y relectura en una transacción. SQLite no tiene FOR UPDATE:
+        // BEGIN IMMEDIATE toma el bloqueo de escritura de la base al empezar (esperando a la
+        // transacción en curso) y lo mantiene hasta el commit. Al retornar antes del commit
+        // la transacción se descarta con un rollback.
+        let mut connection = self.acquire().await?;
+        let mut tx = connection.begin_with("BEGIN IMMEDIATE").await?;
+
+        let Some(existing) = sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+        else {
+            return Ok(None);
+        };
+        check_version(existing.version, version)?;
+
+        // Sin cambios solo se retorna el registro actual
+        if changes.is_empty() {
+            return Ok(Some(existing));
+        }
+
+        // Con la base bloqueada la versión ya está verificada
+        update_query::<Sqlite>(id, changes)
+            .build()
+            .execute(&mut *tx)
+            .await?;
+
+        // Leer de nuevo para incluir la fecha asignada por el trigger
+        let alumno = sqlx::query_as::<_, Alumno>("SELECT * FROM alumnos WHERE id = ?")
+            .bind(id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(Some(alumno))
+    }
+}
@@ -0,0 +1,51 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+/// Cabecera con el identificador de la request, recibida del cliente o generada
+pub const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longitud máxima aceptada para un `X-Request-Id` recibido
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Identificador de la request en curso, disponible dentro del middleware `request_id`
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// Acepta el `X-Request-Id` del cliente (o genera un UUID v4 si falta o no es válido),
+/// lo deja en la request para el span de `TraceLayer` (`telemetry::RequestSpan`), lo expone a `AppError` durante
+/// el handler y lo devuelve en la respuesta.
+pub async fn request_id(mut request: Request, next: Next) -> Response {
+    let id = request
+        .headers()
+        .get(&X_REQUEST_ID)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid(value))
+        .map(str::to_string)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    let header = HeaderValue::from_str(&id).expect("el id solo contiene caracteres visibles");
+    request.headers_mut().insert(X_REQUEST_ID, header.clone());
+
+    let mut response = REQUEST_ID.scope(id, next.run(request)).await;
+    response.headers_mut().insert(X_REQUEST_ID, header);
+    response
+}
+
+/// Solo se aceptan ids cortos de caracteres visibles, para que no ensucien los logs
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'))
+}
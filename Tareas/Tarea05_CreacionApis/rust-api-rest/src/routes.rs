@@ -0,0 +1,36 @@
+use axum::{
+    Router,
+    routing::{delete, get, patch, post, put},
+};
+
+use crate::{handlers::*, state::AppState};
+
+/// Rutas de la API de alumnos, montadas bajo `/api` por la aplicación principal
+pub fn api_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/alumnos", get(get_alumnos))
+        .route("/alumnos", post(create_alumno))
+        .route("/alumnos/duplicados", get(get_duplicados))
+        .route("/alumnos/{id}", get(get_alumno))
+        .route("/alumnos/{id}", put(update_alumno))
+        .route("/alumnos/{id}", patch(patch_alumno))
+        .route("/alumnos/{id}", delete(delete_alumno))
+        .with_state(state)
+}
+
+/// Rutas de health check para el orquestador, montadas en la raíz
+pub fn health_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(liveness))
+        .route("/health", get(liveness))
+        .route("/health/live", get(liveness))
+        .route("/health/ready", get(readiness))
+        .with_state(state)
+}
+
+/// Ruta de métricas de Prometheus, montada en la raíz
+pub fn metrics_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(state)
+}
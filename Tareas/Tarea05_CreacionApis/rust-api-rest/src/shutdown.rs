@@ -0,0 +1,106 @@
+use std::{future::Future, io, sync::Arc, time::Duration};
+
+use axum::Router;
+use tokio::{net::TcpListener, sync::Notify, time::Instant};
+
+use crate::state::AppState;
+
+/// Espera SIGINT (Ctrl+C) o SIGTERM (el que envían Docker y Kubernetes al detener)
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %e, "No se pudo escuchar Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "No se pudo escuchar SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Sirve `app` hasta que `signal` se complete. Al apagar, readiness pasa a 503 y se sigue
+/// atendiendo durante `shutdown_delay`, para que el balanceador vea el 503 y deje de enviar
+/// tráfico; después se deja de aceptar conexiones, se espera a las requests en curso y se
+/// cierran las conexiones a la base de datos, ambos pasos dentro de `drain_timeout`.
+pub async fn serve_with_shutdown<F>(
+    listener: TcpListener,
+    app: Router,
+    state: AppState,
+    shutdown_delay: Duration,
+    drain_timeout: Duration,
+    signal: F,
+) -> io::Result<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let draining = Arc::new(Notify::new());
+
+    let server = axum::serve(listener, app).with_graceful_shutdown({
+        let state = state.clone();
+        let draining = draining.clone();
+        async move {
+            signal.await;
+            state.begin_shutdown();
+            tracing::info!(
+                "🛑 Apagando: readiness responde 503; se dejan de aceptar conexiones en {} s",
+                shutdown_delay.as_secs()
+            );
+            tokio::time::sleep(shutdown_delay).await;
+            tracing::info!(
+                "🛑 Se esperan las requests en curso (máximo {} s)",
+                drain_timeout.as_secs()
+            );
+            draining.notify_one();
+        }
+    });
+    let mut server = tokio::spawn(server.into_future());
+
+    // Sin señal de apagado el servidor solo termina si falla
+    let finished = tokio::select! {
+        result = &mut server => Some(result),
+        _ = draining.notified() => None,
+    };
+    // Un solo plazo para drenar las requests y cerrar el pool
+    let deadline = Instant::now() + drain_timeout;
+    let finished = match finished {
+        Some(result) => Some(result),
+        None => tokio::time::timeout_at(deadline, &mut server).await.ok(),
+    };
+
+    match finished {
+        Some(result) => result.map_err(io::Error::other)??,
+        None => {
+            tracing::warn!("⏱️ Tiempo de drenado agotado, se cortan las requests restantes");
+            server.abort();
+        }
+    }
+
+    // Pool::close espera las conexiones en uso; una request colgada no debe bloquear la salida
+    if tokio::time::timeout_at(deadline, state.alumnos.close())
+        .await
+        .is_err()
+    {
+        tracing::warn!("⏱️ Tiempo agotado al cerrar las conexiones a la base de datos");
+    }
+
+    tracing::info!("👋 Servidor detenido");
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::{ConcurrencySettings, DatabaseSettings, DuplicatesSettings, PaginationSettings},
+    repository::AlumnoRepository,
+};
+
+/// Estado compartido entre todos los handlers
+#[derive(Clone)]
+pub struct AppState {
+    pub alumnos: Arc<dyn AlumnoRepository>,
+    pub pagination: PaginationSettings,
+    pub concurrency: ConcurrencySettings,
+    pub duplicates: DuplicatesSettings,
+    /// Momento de arranque, para reportar el uptime
+    pub started_at: Instant,
+    /// Tiempo máximo de la verificación de la base de datos en readiness
+    pub ping_timeout: Duration,
+    /// Se activa al recibir la señal de apagado; readiness responde 503 desde entonces
+    shutting_down: Arc<AtomicBool>,
+}
+
+impl AppState {
+    pub fn new(alumnos: Arc<dyn AlumnoRepository>) -> Self {
+        Self {
+            alumnos,
+            pagination: PaginationSettings::default(),
+            concurrency: ConcurrencySettings::default(),
+            duplicates: DuplicatesSettings::default(),
+            started_at: Instant::now(),
+            ping_timeout: DatabaseSettings::default().ping_timeout(),
+            shutting_down: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Reemplaza los límites de paginación por los de la configuración
+    pub fn with_pagination(mut self, pagination: PaginationSettings) -> Self {
+        self.pagination = pagination;
+        self
+    }
+
+    /// Reemplaza la configuración de `If-Match` por la de la configuración
+    pub fn with_concurrency(mut self, concurrency: ConcurrencySettings) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Reemplaza la configuración de la detección de duplicados por la de la configuración
+    pub fn with_duplicates(mut self, duplicates: DuplicatesSettings) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Reemplaza el tiempo máximo de la verificación de readiness
+    pub fn with_ping_timeout(mut self, ping_timeout: Duration) -> Self {
+        self.ping_timeout = ping_timeout;
+        self
+    }
+
+    /// Marca la aplicación como en apagado (compartido por todos los clones)
+    pub fn begin_shutdown(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.shutting_down.load(Ordering::SeqCst)
+    }
+}
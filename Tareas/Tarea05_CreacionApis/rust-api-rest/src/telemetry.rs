@@ -0,0 +1,295 @@
+use std::{
+    fmt::Debug,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use axum::http::{HeaderMap, Request, Response};
+use opentelemetry::{
+    Context as OtelContext, KeyValue,
+    propagation::{Extractor, TextMapPropagator},
+    trace::{Span as _, SpanKind, Tracer as _, TracerProvider as _},
+};
+use opentelemetry_otlp::{ExporterBuildError, Protocol, SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    Resource,
+    propagation::TraceContextPropagator,
+    trace::{SdkTracer, SdkTracerProvider},
+};
+use tower_http::trace::{MakeSpan, OnResponse};
+use tracing::{
+    Event, Level, Span, Subscriber,
+    field::{Empty, Field, Visit},
+};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{
+    EnvFilter, Layer,
+    filter::{Targets, filter_fn},
+    fmt,
+    fmt::MakeWriter,
+    layer::{Context, SubscriberExt},
+    registry::LookupSpan,
+    util::SubscriberInitExt,
+};
+
+use crate::{
+    config::{LogFormat, LogSettings, OtelSettings, OtlpProtocol},
+    request_id::X_REQUEST_ID,
+};
+
+/// Valor que reemplaza a los datos sensibles en los logs
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Target de los eventos que sqlx emite al terminar cada consulta
+const SQLX_QUERY_TARGET: &str = "sqlx::query";
+
+/// Mantiene el proveedor de trazas mientras la aplicación corre
+#[must_use = "al descartarlo no se envían las trazas pendientes al apagar"]
+pub struct TracingGuard {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl TracingGuard {
+    /// Envía las trazas pendientes al collector y detiene el exportador
+    pub fn shutdown(self) {
+        if let Some(provider) = self.provider
+            && let Err(e) = provider.shutdown()
+        {
+            tracing::warn!(error = %e, "No se pudieron enviar las trazas pendientes");
+        }
+    }
+}
+
+/// Instala el subscriber global: logs filtrados por `RUST_LOG` (o `log.filter`) en el
+/// formato de `log.format` y, si `otel.enabled`, la exportación de trazas por OTLP.
+pub fn init_tracing(
+    log: &LogSettings,
+    otel: &OtelSettings,
+) -> Result<TracingGuard, ExporterBuildError> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&log.filter));
+    let provider = otel.enabled.then(|| tracer_provider(otel)).transpose()?;
+
+    tracing_subscriber::registry()
+        .with(fmt_layer(log.format, std::io::stdout).with_filter(filter))
+        .with(provider.as_ref().map(otel_layer))
+        .init();
+
+    Ok(TracingGuard { provider })
+}
+
+/// Proveedor de trazas que exporta en lotes al collector configurado
+pub fn tracer_provider(settings: &OtelSettings) -> Result<SdkTracerProvider, ExporterBuildError> {
+    let protocol = match settings.protocol {
+        OtlpProtocol::Protobuf => Protocol::HttpBinary,
+        OtlpProtocol::Json => Protocol::HttpJson,
+    };
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&settings.endpoint)
+        .with_protocol(protocol)
+        .with_timeout(settings.export_timeout())
+        .build()?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            Resource::builder()
+                .with_service_name(settings.service_name.clone())
+                .build(),
+        )
+        .build())
+}
+
+/// Capa que envía a OpenTelemetry los spans de la aplicación (requests y handlers)
+/// y crea un span cliente por cada consulta que ejecuta sqlx.
+pub fn otel_layer<S>(provider: &SdkTracerProvider) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+
+    tracing_opentelemetry::layer()
+        .with_tracer(tracer.clone())
+        .with_filter(Targets::new().with_target(env!("CARGO_CRATE_NAME"), Level::INFO))
+        .and_then(
+            QuerySpans { tracer }
+                .with_filter(filter_fn(|metadata| metadata.target() == SQLX_QUERY_TARGET)),
+        )
+}
+
+/// Capa de formato que escribe en `writer`.
+/// En JSON cada línea lleva timestamp, level, target, los campos del evento en el nivel
+/// superior y los del span de la request en `span`.
+pub fn fmt_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Text => fmt::layer().with_writer(writer).boxed(),
+        LogFormat::Json => fmt::layer()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_writer(writer)
+            .boxed(),
+    }
+}
+
+/// Span de `TraceLayer` para cada request: método, ruta, query sin datos sensibles y
+/// request id. `status` y `latency_ms` se completan en `RecordResponse`.
+/// Si la request trae `traceparent` (W3C), el span continúa esa traza.
+#[derive(Debug, Clone)]
+pub struct RequestSpan {
+    redact: Arc<[String]>,
+}
+
+impl RequestSpan {
+    pub fn new(redact: &[String]) -> Self {
+        Self {
+            redact: redact.into(),
+        }
+    }
+}
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        let request_id = request
+            .headers()
+            .get(&X_REQUEST_ID)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        let span = tracing::info_span!(
+            "request",
+            method = %request.method(),
+            path = %request.uri().path(),
+            query = Empty,
+            version = ?request.version(),
+            request_id = %request_id,
+            status = Empty,
+            latency_ms = Empty,
+            otel.kind = "server",
+        );
+        if let Some(query) = request.uri().query() {
+            span.record("query", redact_query(query, &self.redact));
+        }
+
+        // Sin la capa de OpenTelemetry no hay traza que continuar y el error se ignora
+        let parent = TraceContextPropagator::new().extract(&HeaderExtractor(request.headers()));
+        let _ = span.set_parent(parent);
+        span
+    }
+}
+
+/// Registra el código de estado y la latencia en el span y emite el evento de respuesta
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RecordResponse;
+
+impl<B> OnResponse<B> for RecordResponse {
+    fn on_response(self, response: &Response<B>, latency: Duration, span: &Span) {
+        span.record("status", response.status().as_u16());
+        span.record("latency_ms", latency.as_millis() as u64);
+        tracing::info!("respuesta enviada");
+    }
+}
+
+/// Reemplaza por `[REDACTED]` el valor de los parámetros listados (sin distinguir mayúsculas)
+pub fn redact_query(query: &str, redact: &[String]) -> String {
+    query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if redact.iter().any(|name| name.eq_ignore_ascii_case(key)) => {
+                format!("{key}={REDACTED}")
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Lectura de cabeceras HTTP para el propagador de contexto
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
+
+/// Convierte el evento que sqlx emite al terminar una consulta en un span cliente,
+/// hijo del span activo (el handler), con la sentencia, las filas y la duración.
+struct QuerySpans {
+    tracer: SdkTracer,
+}
+
+impl<S: Subscriber> Layer<S> for QuerySpans {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut query = QueryFields::default();
+        event.record(&mut query);
+
+        let end = SystemTime::now();
+        let start = end
+            .checked_sub(Duration::from_secs_f64(query.elapsed_secs))
+            .unwrap_or(end);
+        let statement = if query.statement.trim().is_empty() {
+            query.summary.clone()
+        } else {
+            query.statement.trim().to_string()
+        };
+
+        let mut span = self
+            .tracer
+            .span_builder(query.summary)
+            .with_kind(SpanKind::Client)
+            .with_start_time(start)
+            .with_attributes([
+                KeyValue::new("db.statement", statement),
+                KeyValue::new("db.rows_affected", query.rows_affected as i64),
+                KeyValue::new("db.rows_returned", query.rows_returned as i64),
+            ])
+            .start_with_context(&self.tracer, &OtelContext::current());
+        span.end_with_timestamp(end);
+    }
+}
+
+/// Campos del evento `sqlx::query`
+#[derive(Default)]
+struct QueryFields {
+    summary: String,
+    statement: String,
+    rows_affected: u64,
+    rows_returned: u64,
+    elapsed_secs: f64,
+}
+
+impl Visit for QueryFields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "summary" => self.summary = value.to_string(),
+            "db.statement" => self.statement = value.to_string(),
+            _ => {}
+        }
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "rows_affected" => self.rows_affected = value,
+            "rows_returned" => self.rows_returned = value,
+            _ => {}
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = value;
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn Debug) {}
+}
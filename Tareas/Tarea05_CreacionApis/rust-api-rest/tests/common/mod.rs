@@ -0,0 +1,136 @@
+//! Utilidades compartidas por las pruebas de integración: requests a la aplicación y datos
+//! de ejemplo. Cada archivo de pruebas usa solo algunas.
+#![allow(dead_code)]
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    http::{HeaderMap, Request, StatusCode, header},
+};
+use http_body_util::BodyExt;
+use serde_json::{Value, json};
+use tower::ServiceExt;
+
+use rust_api_rest::{
+    config::ConcurrencySettings, repository::InMemoryAlumnoRepository, routes, state::AppState,
+};
+
+/// Respuesta completa de una request de prueba
+pub struct TestResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl TestResponse {
+    /// Cuerpo como JSON; `Null` si está vacío o no es JSON
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).unwrap_or(Value::Null)
+    }
+
+    /// Cuerpo como texto
+    pub fn text(&self) -> String {
+        String::from_utf8(self.body.to_vec()).unwrap()
+    }
+
+    /// Valor de una cabecera de la respuesta
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.headers
+            .get(name)
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+}
+
+/// Envía la request con las cabeceras dadas. El cuerpo se envía como JSON salvo que las
+/// cabeceras indiquen otro `Content-Type`.
+pub async fn request(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: Option<Value>,
+) -> TestResponse {
+    let mut request = Request::builder().method(method).uri(uri);
+    if !headers
+        .iter()
+        .any(|(name, _)| name.eq_ignore_ascii_case(header::CONTENT_TYPE.as_str()))
+    {
+        request = request.header(header::CONTENT_TYPE, "application/json");
+    }
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    let request = request
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    TestResponse {
+        status,
+        headers,
+        body,
+    }
+}
+
+/// Envía la request con el cuerpo como JSON; devuelve el status y el cuerpo de la respuesta
+pub async fn send(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    let response = request(app, method, uri, &[], body).await;
+    (response.status, response.json())
+}
+
+/// Envía la request con las cabeceras dadas; devuelve el status, el ETag y el cuerpo
+pub async fn send_with_headers(
+    app: &Router,
+    method: &str,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: Option<Value>,
+) -> (StatusCode, Option<String>, Value) {
+    let response = request(app, method, uri, headers, body).await;
+    (
+        response.status,
+        response.header(header::ETAG.as_str()),
+        response.json(),
+    )
+}
+
+/// Aplicación en memoria con un alumno ya creado (ver `crear_ana`)
+pub async fn app_con_alumno(require_if_match: bool) -> Router {
+    let state = AppState::new(Arc::new(InMemoryAlumnoRepository::new()))
+        .with_concurrency(ConcurrencySettings { require_if_match });
+    let app = Router::new().nest("/api", routes::api_routes(state));
+    crear_ana(&app).await;
+    app
+}
+
+/// Crea a "Ana Pérez" (`ana@email.com`), que en una aplicación vacía queda con ID 1 y
+/// versión 1
+pub async fn crear_ana(app: &Router) {
+    let body = alumno("Ana", "Pérez", "ana@email.com");
+    let (status, etag, _) = send_with_headers(app, "POST", "/api/alumnos", &[], Some(body)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"1\""));
+}
+
+/// Cuerpo válido para crear un alumno; los demás campos se pueden cambiar en el `Value`
+pub fn alumno(nombre: &str, apellido: &str, email: &str) -> Value {
+    json!({
+        "nombre": nombre,
+        "apellido": apellido,
+        "email": email,
+        "edad": 21,
+        "carrera": "Ingeniería en Sistemas",
+        "semestre": 5,
+        "promedio": 8.5
+    })
+}
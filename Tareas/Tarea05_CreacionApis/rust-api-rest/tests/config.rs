@@ -0,0 +1,140 @@
+use rust_api_rest::config::{LogFormat, Settings, SettingsError};
+
+#[test]
+fn valores_por_defecto_son_validos() {
+    let settings = Settings::default();
+
+    assert!(settings.validate().is_ok());
+    assert_eq!(settings.server.port, 3000);
+    assert_eq!(settings.pagination.max_limit, 100);
+}
+
+#[test]
+fn archivo_incluido_coincide_con_los_valores_por_defecto() {
+    let settings = Settings::from_toml(include_str!("../config/default.toml")).unwrap();
+
+    assert_eq!(settings, Settings::default());
+}
+
+#[test]
+fn toml_parcial_conserva_el_resto_de_valores() {
+    let settings = Settings::from_toml(
+        r#"
+        [server]
+        port = 8080
+
+        [log]
+        format = "json"
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(settings.server.port, 8080);
+    assert_eq!(settings.server.host, "0.0.0.0");
+    assert_eq!(settings.log.format, LogFormat::Json);
+    assert_eq!(settings.database.max_connections, 10);
+}
+
+#[test]
+fn tipos_invalidos_fallan_al_cargar() {
+    for toml in [
+        "[server]\nport = 70000",
+        "[server]\nport = \"abc\"",
+        "[log]\nformat = \"xml\"",
+        "[database]\nmax_conexiones = 5",
+    ] {
+        let result = Settings::from_toml(toml);
+        assert!(
+            matches!(result, Err(SettingsError::Load(_))),
+            "{toml:?} debería fallar, se obtuvo {result:?}"
+        );
+    }
+}
+
+#[test]
+fn secciones_desconocidas_fallan_al_cargar() {
+    for toml in [
+        "[databse]\nurl = \"mysql://localhost/alumnos\"",
+        "puerto = 8080",
+    ] {
+        let result = Settings::from_toml(toml);
+        assert!(
+            matches!(result, Err(SettingsError::Load(_))),
+            "{toml:?} debería fallar, se obtuvo {result:?}"
+        );
+    }
+}
+
+#[test]
+fn reglas_invalidas_se_reportan_juntas() {
+    let result = Settings::from_toml(
+        r#"
+        [database]
+        max_connections = 2
+        min_connections = 5
+
+        [cors]
+        allowed_origins = ["localhost:3000"]
+
+        [otel]
+        enabled = true
+        endpoint = "localhost:4318"
+
+        [pagination]
+        default_limit = 50
+        max_limit = 20
+        "#,
+    );
+
+    let Err(SettingsError::Invalid(errors)) = result else {
+        panic!("se esperaba SettingsError::Invalid, se obtuvo {result:?}");
+    };
+    assert_eq!(errors.len(), 4);
+    assert!(errors[0].starts_with("database.min_connections"));
+    assert!(errors[1].starts_with("cors.allowed_origins"));
+    assert!(errors[2].starts_with("otel.endpoint"));
+    assert!(errors[3].starts_with("pagination.max_limit"));
+}
+
+#[test]
+fn load_combina_archivo_y_variables_de_entorno() {
+    let path = std::env::temp_dir().join(format!("alumnos-config-{}.toml", std::process::id()));
+    std::fs::write(&path, "[log]\nformat = \"json\"\n").unwrap();
+
+    // SAFETY: es la única prueba de este binario que lee o modifica el entorno
+    unsafe {
+        for name in ["DATABASE_URL", "SERVER_HOST", "DATABASE_AUTO_MIGRATE"] {
+            std::env::remove_var(name);
+        }
+        std::env::set_var("APP_CONFIG", &path);
+        std::env::set_var("APP_SERVER__PORT", "8080");
+        std::env::set_var("APP_PAGINATION__MAX_LIMIT", "50");
+        std::env::set_var("SERVER_PORT", "9090");
+        // Variables que Kubernetes define para un Service llamado `app`
+        std::env::set_var("APP_SERVICE_HOST", "10.0.0.1");
+        std::env::set_var("APP_PORT", "tcp://10.0.0.1:80");
+    }
+
+    // La variable histórica tiene prioridad sobre APP_SERVER__PORT
+    let settings = Settings::load().unwrap();
+    assert_eq!(settings.server.port, 9090);
+    assert_eq!(settings.pagination.max_limit, 50);
+    assert_eq!(settings.log.format, LogFormat::Json);
+
+    unsafe { std::env::remove_var("SERVER_PORT") };
+    let settings = Settings::load().unwrap();
+    assert_eq!(settings.server.port, 8080);
+
+    unsafe {
+        for name in [
+            "APP_CONFIG",
+            "APP_SERVER__PORT",
+            "APP_PAGINATION__MAX_LIMIT",
+            "APP_SERVICE_HOST",
+            "APP_PORT",
+        ] {
+            std::env::remove_var(name);
+        }
+    }
+    std::fs::remove_file(&path).unwrap();
+}
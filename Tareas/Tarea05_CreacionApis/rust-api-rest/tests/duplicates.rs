@@ -0,0 +1,236 @@
+use std::sync::Arc;
+
+use axum::{Router, http::StatusCode};
+use serde_json::{Value, json};
+
+use rust_api_rest::{
+    config::DuplicatesSettings,
+    database::{DUPLICATE_BATCH, DUPLICATE_THRESHOLD, Identity, similarity},
+    repository::InMemoryAlumnoRepository,
+    routes,
+    state::AppState,
+};
+
+mod common;
+use common::{alumno, send};
+
+fn app(duplicates: DuplicatesSettings) -> Router {
+    let state =
+        AppState::new(Arc::new(InMemoryAlumnoRepository::new())).with_duplicates(duplicates);
+    Router::new().nest("/api", routes::api_routes(state))
+}
+
+fn persona<'a>(nombre: &'a str, apellido: &'a str, email: &'a str, edad: i32) -> Identity<'a> {
+    Identity {
+        nombre,
+        apellido,
+        email,
+        edad,
+    }
+}
+
+fn con_edad(mut alumno: Value, edad: i32) -> Value {
+    alumno["edad"] = json!(edad);
+    alumno
+}
+
+#[test]
+fn similitud_tolera_errores_de_tipeo_acentos_y_orden() {
+    let juan = persona("Juan", "Pérez", "juan.perez@email.com", 20);
+
+    // Letras transpuestas, sin acento y con otro dominio
+    let tipeo = similarity(juan, persona("Jaun", "Perez", "juanperez@gmail.com", 20));
+    assert_eq!(tipeo.nombre, 0.9);
+    assert_eq!(tipeo.email, 1.0);
+    assert_eq!(tipeo.edad, 1.0);
+    assert_eq!(tipeo.score, 0.94);
+
+    // Nombre y apellido invertidos, un año de diferencia
+    let invertido = similarity(juan, persona("PÉREZ", "juan", "jperez@email.com", 21));
+    assert_eq!(invertido.nombre, 1.0);
+    assert_eq!(invertido.edad, 0.5);
+    assert!(invertido.score >= DUPLICATE_THRESHOLD, "{invertido:?}");
+
+    let otra = similarity(juan, persona("Lucía", "Fernández", "lucia.f@email.com", 20));
+    assert!(otra.score < 0.5, "{otra:?}");
+}
+
+#[test]
+fn solo_se_comparan_alumnos_cuyos_nombres_empiezan_igual() {
+    let juan = persona("José Luis", "Pérez", "jl@email.com", 20);
+    assert_eq!(juan.blocking_keys(), ["jos", "lui", "per"]);
+    assert_eq!(
+        persona("Li", "Wu", "li@email.com", 20).blocking_keys(),
+        ["li", "wu"]
+    );
+
+    // Cualquier palabra con uno de los prefijos basta
+    let search = juan.candidates_search();
+    assert_eq!(search.boolean_mode(), "jos* lui* per*");
+    assert_eq!(search.tsquery(), "jos:* | lui:* | per:*");
+    assert_eq!(
+        search.fts5(),
+        "{nombre apellido} : (\"jos\"* OR \"lui\"* OR \"per\"*)"
+    );
+}
+
+#[tokio::test]
+async fn crear_un_posible_duplicado_avisa_sin_bloquear() {
+    let app = app(DuplicatesSettings::default());
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Andrés", "Gómez", "andres.gomez@email.com")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Mismo alumno con un error de tipeo: se crea y se informan los parecidos, sin su email
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Andres", "Gomes", "andresgomez@universidad.edu")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["id"], 2);
+    let duplicados = body["duplicados"].as_array().unwrap();
+    assert_eq!(duplicados.len(), 1);
+    assert_eq!(duplicados[0]["id"], 1);
+    assert_eq!(duplicados[0]["nombre"], "Andrés");
+    assert!(duplicados[0].get("email").is_none());
+    assert!(duplicados[0]["similitud"]["score"].as_f64().unwrap() >= DUPLICATE_THRESHOLD);
+
+    // Otra persona no recibe sugerencias
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Lucía", "Fernández", "lucia@email.com")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.get("duplicados").is_none());
+}
+
+#[tokio::test]
+async fn block_on_create_rechaza_un_posible_duplicado_salvo_con_force() {
+    let app = app(DuplicatesSettings {
+        block_on_create: true,
+    });
+    let (status, _) = send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Andrés", "Gómez", "andres.gomez@email.com")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // 409 con las sugerencias, identificadas solo por ID y nombre
+    let repetido = alumno("Andres", "Gomes", "andresgomez@universidad.edu");
+    let (status, body) = send(&app, "POST", "/api/alumnos", Some(repetido.clone())).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["success"], false);
+    let duplicados = body["duplicados"].as_array().unwrap();
+    assert_eq!(duplicados.len(), 1);
+    assert_eq!(duplicados[0]["id"], 1);
+    assert_eq!(duplicados[0]["apellido"], "Gómez");
+    assert!(duplicados[0].get("email").is_none());
+    assert!(duplicados[0].get("alumno").is_none());
+
+    // Con force=true se crea de todos modos, sin buscar parecidos
+    let (status, body) = send(&app, "POST", "/api/alumnos?force=true", Some(repetido)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["apellido"], "Gomes");
+    assert!(body.get("duplicados").is_none());
+
+    // Los errores que no son duplicados no llevan el campo
+    let (status, body) = send(&app, "GET", "/api/alumnos/999", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body.get("duplicados").is_none());
+}
+
+#[tokio::test]
+async fn duplicados_lista_los_pares_mas_parecidos_primero() {
+    let app = app(DuplicatesSettings::default());
+    for body in [
+        con_edad(alumno("María", "López", "maria.lopez@email.com"), 19),
+        con_edad(alumno("Carlos", "Ruiz", "carlos.ruiz@email.com"), 24),
+        con_edad(alumno("Maria", "Lopez", "marialopez@gmail.com"), 19),
+        con_edad(alumno("Carlos", "Ruíz", "cruiz@email.com"), 25),
+        con_edad(alumno("Sofía", "Herrera", "sofia@email.com"), 30),
+    ] {
+        let (status, _) = send(&app, "POST", "/api/alumnos", Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, body) = send(&app, "GET", "/api/alumnos/duplicados", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 2);
+    let pares: Vec<(i64, i64)> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|par| {
+            (
+                par["alumno"]["id"].as_i64().unwrap(),
+                par["duplicado"]["id"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(pares, [(1, 3), (2, 4)]);
+    assert_eq!(body["data"][0]["similitud"]["score"], 1.0);
+
+    // Un umbral más exigente deja solo el par idéntico
+    let (_, body) = send(&app, "GET", "/api/alumnos/duplicados?umbral=0.95", None).await;
+    assert_eq!(body["total"], 1);
+
+    // limit recorta los pares devueltos pero total sigue contándolos todos
+    let (_, body) = send(&app, "GET", "/api/alumnos/duplicados?limit=1", None).await;
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["limit"], 1);
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+    assert_eq!(body["data"][0]["duplicado"]["id"], 3);
+
+    // Alumnos que no comparten el inicio de ninguna palabra no se comparan
+    let (_, body) = send(&app, "GET", "/api/alumnos/duplicados?umbral=0", None).await;
+    let pares = body["data"].as_array().unwrap();
+    assert!(
+        pares
+            .iter()
+            .all(|par| par["alumno"]["nombre"] != "Sofía" && par["duplicado"]["nombre"] != "Sofía"),
+        "{pares:?}"
+    );
+
+    let (status, _) = send(&app, "GET", "/api/alumnos/duplicados?umbral=1.5", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn duplicados_compara_alumnos_de_distintos_lotes() {
+    let app = app(DuplicatesSettings::default());
+    let mut alumnos = vec![alumno("Juan", "Pérez", "juan.perez@email.com")];
+    // Un lote completo de alumnos que no comparten claves con nadie ("Qab Qabson", ...)
+    for n in 0..DUPLICATE_BATCH as u8 {
+        let clave = format!("Q{}{}", (b'a' + n / 26) as char, (b'a' + n % 26) as char);
+        alumnos.push(alumno(
+            &clave,
+            &format!("{clave}son"),
+            &format!("q{n}@email.com"),
+        ));
+    }
+    alumnos.push(alumno("Jaun", "Perez", "juanperez@gmail.com"));
+    for body in alumnos {
+        let (status, _) = send(&app, "POST", "/api/alumnos", Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, body) = send(&app, "GET", "/api/alumnos/duplicados", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["data"][0]["alumno"]["id"], 1);
+    assert_eq!(body["data"][0]["duplicado"]["id"], DUPLICATE_BATCH + 2);
+}
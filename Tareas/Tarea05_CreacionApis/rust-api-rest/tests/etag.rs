@@ -0,0 +1,188 @@
+use axum::http::StatusCode;
+use serde_json::{Value, json};
+
+mod common;
+use common::{app_con_alumno, send_with_headers};
+
+fn alumno(nombre: &str) -> Value {
+    common::alumno(nombre, "Pérez", "ana@email.com")
+}
+
+#[tokio::test]
+async fn get_devuelve_etag_y_304_si_no_cambio() {
+    let app = app_con_alumno(false).await;
+
+    let (status, etag, body) = send_with_headers(&app, "GET", "/api/alumnos/1", &[], None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"1\""));
+    assert_eq!(body["data"]["version"], 1);
+
+    // Con fields el ETag se mantiene aunque no se pida la versión
+    let (_, etag, body) =
+        send_with_headers(&app, "GET", "/api/alumnos/1?fields=nombre", &[], None).await;
+    assert_eq!(etag.as_deref(), Some("\"1\""));
+    assert_eq!(body["data"], json!({"nombre": "Ana"}));
+
+    // If-None-Match usa comparación débil: W/"1" también coincide
+    for condicion in ["\"1\"", "W/\"1\"", "\"7\", \"1\"", "*"] {
+        let (status, etag, body) = send_with_headers(
+            &app,
+            "GET",
+            "/api/alumnos/1",
+            &[("if-none-match", condicion)],
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_MODIFIED, "{condicion}");
+        assert_eq!(etag.as_deref(), Some("\"1\""));
+        assert_eq!(body, Value::Null);
+    }
+
+    // Después de un cambio la versión anterior ya no coincide
+    let (status, etag, _) = send_with_headers(
+        &app,
+        "PATCH",
+        "/api/alumnos/1",
+        &[],
+        Some(json!({"semestre": 6})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"2\""));
+
+    let (status, etag, body) = send_with_headers(
+        &app,
+        "GET",
+        "/api/alumnos/1",
+        &[("if-none-match", "\"1\"")],
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"2\""));
+    assert_eq!(body["data"]["semestre"], 6);
+}
+
+#[tokio::test]
+async fn if_match_desactualizado_no_sobrescribe() {
+    let app = app_con_alumno(false).await;
+
+    // Dos secretarias leen la versión 1; la primera guarda y la segunda recibe 412
+    let (status, etag, body) = send_with_headers(
+        &app,
+        "PUT",
+        "/api/alumnos/1",
+        &[("if-match", "\"1\"")],
+        Some(alumno("Ana María")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"2\""));
+    assert_eq!(body["data"]["version"], 2);
+
+    let (status, _, body) = send_with_headers(
+        &app,
+        "PATCH",
+        "/api/alumnos/1",
+        &[("if-match", "\"1\"")],
+        Some(json!({"nombre": "Anita"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+    assert_eq!(body["success"], false);
+
+    let (status, _, _) = send_with_headers(
+        &app,
+        "DELETE",
+        "/api/alumnos/1",
+        &[("if-match", "\"1\"")],
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    // If-Match usa comparación fuerte: un ETag débil nunca coincide
+    let (status, _, _) = send_with_headers(
+        &app,
+        "PATCH",
+        "/api/alumnos/1",
+        &[("if-match", "W/\"2\"")],
+        Some(json!({"nombre": "Anita"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    let (_, _, body) = send_with_headers(&app, "GET", "/api/alumnos/1", &[], None).await;
+    assert_eq!(body["data"]["nombre"], "Ana María");
+
+    // Con la versión actual (o *) la escritura se aplica
+    let (status, etag, _) = send_with_headers(
+        &app,
+        "PATCH",
+        "/api/alumnos/1",
+        &[("if-match", "\"1\", \"2\"")],
+        Some(json!({"nombre": "Anita"})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(etag.as_deref(), Some("\"3\""));
+
+    let (status, _, _) =
+        send_with_headers(&app, "DELETE", "/api/alumnos/1", &[("if-match", "*")], None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Con If-Match (aun `*`) un alumno que no existe no cumple la condición: 412
+    for (method, condicion, body) in [
+        ("DELETE", "\"3\"", None),
+        ("DELETE", "*", None),
+        ("PUT", "*", Some(alumno("Ana"))),
+        ("PATCH", "*", Some(json!({"semestre": 6}))),
+    ] {
+        let (status, _, _) = send_with_headers(
+            &app,
+            method,
+            "/api/alumnos/1",
+            &[("if-match", condicion)],
+            body,
+        )
+        .await;
+        assert_eq!(
+            status,
+            StatusCode::PRECONDITION_FAILED,
+            "{method} {condicion}"
+        );
+    }
+
+    // Sin la cabecera sigue siendo 404
+    let (status, _, _) = send_with_headers(&app, "DELETE", "/api/alumnos/1", &[], None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn require_if_match_rechaza_escrituras_sin_condicion() {
+    let app = app_con_alumno(true).await;
+
+    for (method, body) in [
+        ("PUT", Some(alumno("Ana María"))),
+        ("PATCH", Some(json!({"semestre": 6}))),
+        ("DELETE", None),
+    ] {
+        let (status, _, body) = send_with_headers(&app, method, "/api/alumnos/1", &[], body).await;
+        assert_eq!(status, StatusCode::PRECONDITION_REQUIRED, "{method}");
+        assert_eq!(body["success"], false);
+    }
+
+    // Las lecturas y la creación no requieren la cabecera
+    let (status, _, _) = send_with_headers(&app, "GET", "/api/alumnos/1", &[], None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _, _) = send_with_headers(
+        &app,
+        "PATCH",
+        "/api/alumnos/1",
+        &[("if-match", "\"1\"")],
+        Some(json!({"semestre": 6})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -0,0 +1,460 @@
+use std::cmp::Ordering;
+
+use chrono::{TimeZone, Utc};
+use rust_api_rest::{
+    database::{
+        AlumnoFilter, Cursor, FieldSet, PageStart, SearchQuery, Sort, SortValue, escape_like, fold,
+    },
+    error::AppError,
+    models::{PaginationQuery, PartialAlumno},
+};
+use sqlx::MySql;
+
+fn query(search: Option<&str>, carrera: Option<&str>, activo: Option<bool>) -> PaginationQuery {
+    PaginationQuery {
+        search: search.map(str::to_string),
+        carrera: carrera.map(str::to_string),
+        activo,
+        ..PaginationQuery::default()
+    }
+}
+
+#[test]
+fn sin_filtros_no_agrega_condiciones() {
+    let filter = AlumnoFilter::from(&query(None, None, None));
+
+    assert_eq!(
+        filter.count_query::<MySql>().sql(),
+        "SELECT COUNT(*) AS total FROM alumnos WHERE 1=1"
+    );
+    assert_eq!(
+        filter
+            .select_query::<MySql>(
+                &FieldSet::default(),
+                &Sort::default(),
+                &PageStart::Offset(0),
+                10
+            )
+            .sql(),
+        "SELECT * FROM alumnos WHERE 1=1 ORDER BY fecha_registro DESC, id DESC LIMIT ? OFFSET ?"
+    );
+}
+
+#[test]
+fn filtros_vacios_se_ignoran() {
+    let filter = AlumnoFilter::from(&query(Some("   "), Some(""), None));
+
+    assert_eq!(filter, AlumnoFilter::default());
+}
+
+#[test]
+fn todos_los_filtros_usan_parametros() {
+    let filter = AlumnoFilter::from(&query(
+        Some("Mendoza"),
+        Some("Ingeniería Civil"),
+        Some(true),
+    ));
+
+    let sql = filter
+        .select_query::<MySql>(
+            &FieldSet::default(),
+            &Sort::default(),
+            &PageStart::Offset(20),
+            10,
+        )
+        .sql()
+        .to_string();
+    assert_eq!(
+        sql,
+        "SELECT * FROM (SELECT alumnos.*, \
+         MATCH(nombre, apellido, email, carrera) AGAINST (? IN BOOLEAN MODE) AS score \
+         FROM alumnos WHERE MATCH(nombre, apellido, email, carrera) AGAINST (? IN BOOLEAN MODE)) \
+         AS busqueda WHERE 1=1 \
+         AND carrera = ? AND activo = ? \
+         ORDER BY fecha_registro DESC, id DESC LIMIT ? OFFSET ?"
+    );
+    assert!(!sql.contains("Mendoza"));
+    assert!(!sql.contains("Ingeniería Civil"));
+}
+
+#[test]
+fn la_consulta_de_conteo_comparte_el_where() {
+    let filter = AlumnoFilter::from(&query(Some("Ana"), Some("Sistemas"), Some(false)));
+
+    let select = filter
+        .select_query::<MySql>(
+            &FieldSet::default(),
+            &Sort::default(),
+            &PageStart::Offset(0),
+            10,
+        )
+        .sql()
+        .to_string();
+    let count = filter.count_query::<MySql>().sql().to_string();
+
+    let where_select = &select[select.find(" WHERE").unwrap()..select.find(" ORDER BY").unwrap()];
+    let where_count = &count[count.find(" WHERE").unwrap()..];
+    assert_eq!(where_select, where_count);
+}
+
+#[test]
+fn entrada_hostil_no_altera_el_sql() {
+    let hostiles = [
+        "' OR '1'='1",
+        "'; DROP TABLE alumnos; --",
+        "\\' OR 1=1 #",
+        "Robert'); DELETE FROM alumnos WHERE ('1'='1",
+    ];
+
+    for hostil in hostiles {
+        let filter = AlumnoFilter::from(&query(Some(hostil), Some(hostil), None));
+
+        for sql in [
+            filter
+                .select_query::<MySql>(
+                    &FieldSet::default(),
+                    &Sort::default(),
+                    &PageStart::Offset(0),
+                    10,
+                )
+                .sql()
+                .to_string(),
+            filter.count_query::<MySql>().sql().to_string(),
+        ] {
+            assert!(!sql.contains(hostil), "entrada filtrada al SQL: {sql}");
+            assert!(!sql.contains("DROP"));
+            assert!(!sql.contains("DELETE"));
+            assert!(!sql.contains('\''), "comillas inesperadas: {sql}");
+        }
+
+        // El valor se conserva intacto para enviarse como parámetro
+        assert_eq!(filter.search.as_deref(), Some(hostil));
+        assert_eq!(filter.carreras, [hostil]);
+    }
+}
+
+#[test]
+fn comodines_like_se_buscan_literalmente() {
+    assert_eq!(escape_like("100%"), "100!%");
+    assert_eq!(escape_like("mi_nombre"), "mi!_nombre");
+    assert_eq!(escape_like("hola!"), "hola!!");
+    assert_eq!(escape_like("O'Brien"), "O'Brien");
+}
+
+#[test]
+fn los_cursores_usan_keyset_con_parametros() {
+    let filter = AlumnoFilter::from(&query(None, None, Some(true)));
+    let cursor = Cursor {
+        sort: "-fecha_registro".to_string(),
+        values: vec![SortValue::Fecha(
+            Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap(),
+        )],
+        id: 7,
+    };
+
+    assert_eq!(
+        filter
+            .select_query::<MySql>(
+                &FieldSet::default(),
+                &Sort::default(),
+                &PageStart::After(cursor.clone()),
+                10
+            )
+            .sql(),
+        "SELECT * FROM alumnos WHERE 1=1 AND activo = ? \
+         AND (fecha_registro < ? OR (fecha_registro = ? AND id < ?)) \
+         ORDER BY fecha_registro DESC, id DESC LIMIT ?"
+    );
+    assert_eq!(
+        filter
+            .select_query::<MySql>(
+                &FieldSet::default(),
+                &Sort::default(),
+                &PageStart::Before(cursor),
+                10
+            )
+            .sql(),
+        "SELECT * FROM (SELECT * FROM alumnos WHERE 1=1 AND activo = ? \
+         AND (fecha_registro > ? OR (fecha_registro = ? AND id > ?)) \
+         ORDER BY fecha_registro ASC, id ASC LIMIT ?) AS pagina \
+         ORDER BY fecha_registro DESC, id DESC"
+    );
+}
+
+#[test]
+fn cursor_se_codifica_de_forma_reversible() {
+    let sort = Sort::parse(Some("apellido,-promedio")).unwrap();
+    let cursor = Cursor {
+        sort: sort.to_string(),
+        values: vec![
+            SortValue::Text("Mendoza".to_string()),
+            SortValue::Float(9.5),
+        ],
+        id: 42,
+    };
+    let encoded = cursor.encode();
+
+    assert!(
+        encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    );
+    assert_eq!(Cursor::decode(&encoded, &sort).unwrap(), cursor);
+    assert!(Cursor::decode("no-es-un-cursor", &sort).is_err());
+    assert!(Cursor::decode(&encoded[1..], &sort).is_err());
+    // Un cursor solo sirve para el orden con el que se generó
+    assert!(Cursor::decode(&encoded, &Sort::default()).is_err());
+}
+
+#[test]
+fn sort_acepta_solo_columnas_permitidas() {
+    let sort = Sort::parse(Some(" apellido, -promedio,,semestre ")).unwrap();
+    assert_eq!(sort.to_string(), "apellido,-promedio,semestre");
+    assert_eq!(Sort::parse(None).unwrap(), Sort::default());
+    assert_eq!(
+        Sort::parse(Some("")).unwrap().to_string(),
+        "-fecha_registro"
+    );
+
+    for invalido in [
+        "password",
+        "apellido;DROP TABLE alumnos",
+        "-",
+        "apellido,-apellido",
+    ] {
+        assert!(Sort::parse(Some(invalido)).is_err(), "aceptó {invalido}");
+    }
+}
+
+#[test]
+fn sort_agrega_el_id_como_desempate() {
+    let filter = AlumnoFilter::default();
+    let sort = Sort::parse(Some("apellido,-promedio")).unwrap();
+
+    assert_eq!(
+        filter
+            .select_query::<MySql>(&FieldSet::default(), &sort, &PageStart::Offset(0), 10)
+            .sql(),
+        "SELECT * FROM alumnos WHERE 1=1 \
+         ORDER BY apellido ASC, promedio DESC, id DESC LIMIT ? OFFSET ?"
+    );
+
+    // Si el cliente ya ordena por id no se repite
+    let sort = Sort::parse(Some("-semestre,id")).unwrap();
+    assert!(
+        filter
+            .select_query::<MySql>(&FieldSet::default(), &sort, &PageStart::Offset(0), 10)
+            .sql()
+            .ends_with("ORDER BY semestre DESC, id ASC LIMIT ? OFFSET ?")
+    );
+}
+
+#[test]
+fn una_columna_de_orden_null_no_genera_cursor() {
+    let sort = Sort::parse(Some("apellido")).unwrap();
+    let con_apellido = PartialAlumno {
+        id: Some(1),
+        apellido: Some("Álvarez".to_string()),
+        ..PartialAlumno::default()
+    };
+    let sin_apellido = PartialAlumno {
+        id: Some(2),
+        ..PartialAlumno::default()
+    };
+
+    // Un cursor no puede guardar NULL: es un error, no un pánico
+    assert!(Cursor::of(&con_apellido, &sort).is_ok());
+    assert!(matches!(
+        Cursor::of(&sin_apellido, &sort),
+        Err(AppError::Database(_))
+    ));
+
+    // Al ordenar, NULL va antes que cualquier valor, como en MySQL
+    assert_eq!(
+        sort.compare_alumnos(&sin_apellido, &con_apellido),
+        Ordering::Less
+    );
+}
+
+#[test]
+fn keyset_respeta_la_direccion_de_cada_columna() {
+    let filter = AlumnoFilter::default();
+    let sort = Sort::parse(Some("apellido,-promedio")).unwrap();
+    let cursor = Cursor {
+        sort: sort.to_string(),
+        values: vec![
+            SortValue::Text("Mendoza".to_string()),
+            SortValue::Float(9.5),
+        ],
+        id: 7,
+    };
+
+    assert_eq!(
+        filter
+            .select_query::<MySql>(
+                &FieldSet::default(),
+                &sort,
+                &PageStart::After(cursor.clone()),
+                10
+            )
+            .sql(),
+        "SELECT * FROM alumnos WHERE 1=1 \
+         AND (apellido > ? OR (apellido = ? AND promedio < ?) \
+         OR (apellido = ? AND promedio = ? AND id < ?)) \
+         ORDER BY apellido ASC, promedio DESC, id DESC LIMIT ?"
+    );
+    assert_eq!(
+        filter
+            .select_query::<MySql>(&FieldSet::default(), &sort, &PageStart::Before(cursor), 10)
+            .sql(),
+        "SELECT * FROM (SELECT * FROM alumnos WHERE 1=1 \
+         AND (apellido < ? OR (apellido = ? AND promedio > ?) \
+         OR (apellido = ? AND promedio = ? AND id > ?)) \
+         ORDER BY apellido DESC, promedio ASC, id ASC LIMIT ?) AS pagina \
+         ORDER BY apellido ASC, promedio DESC, id DESC"
+    );
+}
+
+#[test]
+fn fields_selecciona_solo_las_columnas_pedidas_y_las_del_orden() {
+    let filter = AlumnoFilter::default();
+    let fields = FieldSet::parse(Some("nombre, id,apellido,nombre")).unwrap();
+
+    assert_eq!(fields.select_list(&[]), "id, nombre, apellido");
+    assert_eq!(FieldSet::default().select_list(&[]), "*");
+    assert_eq!(
+        filter
+            .select_query::<MySql>(&fields, &Sort::default(), &PageStart::Offset(0), 10)
+            .sql(),
+        "SELECT id, nombre, apellido, fecha_registro FROM alumnos WHERE 1=1 \
+         ORDER BY fecha_registro DESC, id DESC LIMIT ? OFFSET ?"
+    );
+
+    for invalido in ["password", "nombre,password", "id; DROP TABLE alumnos"] {
+        assert!(
+            FieldSet::parse(Some(invalido)).is_err(),
+            "aceptó {invalido}"
+        );
+    }
+}
+
+#[test]
+fn busqueda_normaliza_palabras_y_arma_la_consulta_de_cada_motor() {
+    assert_eq!(fold("Andrés ÑANDÚ Çelik"), "andres nandu celik");
+
+    let search = SearchQuery::new("  Andrés O'Brien! +gom* ");
+    assert_eq!(search.terms(), ["andres", "o", "brien", "gom"]);
+    assert_eq!(search.boolean_mode(), "+andres* +o* +brien* +gom*");
+    assert_eq!(search.tsquery(), "andres:* & o:* & brien:* & gom:*");
+    assert_eq!(search.fts5(), "\"andres\"* \"o\"* \"brien\"* \"gom\"*");
+
+    // Sin palabras no hay nada que buscar ni que enviar al motor
+    let filter = AlumnoFilter::from(&query(Some("%_!"), None, None));
+    assert!(filter.search().unwrap().terms().is_empty());
+    assert_eq!(
+        filter.count_query::<MySql>().sql(),
+        "SELECT COUNT(*) AS total FROM \
+         (SELECT alumnos.*, 0 AS score FROM alumnos WHERE 1 = 0) AS busqueda WHERE 1=1"
+    );
+
+    // Con campos parciales también se lee la relevancia
+    let filter = AlumnoFilter::from(&query(Some("ana"), None, None));
+    let sort = Sort::from_query(None, true).unwrap();
+    let sql = filter
+        .select_query::<MySql>(
+            &FieldSet::parse(Some("nombre")).unwrap(),
+            &sort,
+            &PageStart::Offset(0),
+            10,
+        )
+        .sql()
+        .to_string();
+    assert!(sql.starts_with("SELECT id, nombre, score FROM (SELECT alumnos.*"));
+    assert!(sql.ends_with("ORDER BY score DESC, id DESC LIMIT ? OFFSET ?"));
+}
+
+#[test]
+fn score_solo_se_ordena_al_buscar() {
+    assert_eq!(Sort::from_query(None, true).unwrap(), Sort::relevance());
+    assert_eq!(Sort::from_query(None, false).unwrap(), Sort::default());
+    assert_eq!(
+        Sort::from_query(Some("apellido"), true)
+            .unwrap()
+            .to_string(),
+        "apellido"
+    );
+    assert_eq!(
+        Sort::from_query(Some("-score,nombre"), true)
+            .unwrap()
+            .to_string(),
+        "-score,nombre"
+    );
+    assert!(Sort::parse(Some("-score")).is_err());
+    assert!(FieldSet::parse(Some("score")).is_err());
+}
+
+#[test]
+fn rangos_listas_y_dominios_usan_parametros() {
+    let filter = AlumnoFilter::from(&PaginationQuery {
+        carrera: Some("Ingeniería Civil, Ingeniería en Sistemas,".to_string()),
+        edad_min: Some(18),
+        semestre_max: Some(4),
+        promedio_min: Some(8.0),
+        promedio_max: Some(9.5),
+        registrado_desde: Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()),
+        email_dominio: Some("@universidad.edu,alumnos.universidad.edu".to_string()),
+        ..PaginationQuery::default()
+    });
+
+    assert_eq!(
+        filter.carreras,
+        ["Ingeniería Civil", "Ingeniería en Sistemas"]
+    );
+    assert_eq!(
+        filter.email_dominios,
+        ["universidad.edu", "alumnos.universidad.edu"]
+    );
+    assert_eq!(
+        filter.count_query::<MySql>().sql(),
+        "SELECT COUNT(*) AS total FROM alumnos WHERE 1=1 \
+         AND carrera IN (?, ?) \
+         AND edad >= ? AND semestre <= ? AND promedio >= ? AND promedio <= ? \
+         AND fecha_registro >= ? \
+         AND (email LIKE ? ESCAPE '!' OR email LIKE ? ESCAPE '!')"
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn postgres_usa_placeholders_numerados_e_ilike() {
+    let filter = AlumnoFilter::from(&query(Some("ana"), Some("Ingeniería Civil"), Some(true)));
+
+    assert_eq!(
+        filter
+            .select_query::<sqlx::Postgres>(
+                &FieldSet::default(),
+                &Sort::default(),
+                &PageStart::Offset(0),
+                10
+            )
+            .sql(),
+        "SELECT * FROM (SELECT alumnos.*, \
+         ts_rank(alumnos_documento(nombre, apellido, email, carrera), to_tsquery('simple', $1))::DOUBLE PRECISION AS score \
+         FROM alumnos WHERE alumnos_documento(nombre, apellido, email, carrera) @@ to_tsquery('simple', $2)) \
+         AS busqueda WHERE 1=1 \
+         AND LOWER(carrera) = LOWER($3) AND activo = $4 \
+         ORDER BY fecha_registro DESC, id DESC LIMIT $5 OFFSET $6"
+    );
+
+    let filter = AlumnoFilter::from(&PaginationQuery {
+        carrera: Some("Ingeniería Civil,Ingeniería en Sistemas".to_string()),
+        email_dominio: Some("universidad.edu".to_string()),
+        ..PaginationQuery::default()
+    });
+    assert_eq!(
+        filter.count_query::<sqlx::Postgres>().sql(),
+        "SELECT COUNT(*) AS total FROM alumnos WHERE 1=1 \
+         AND LOWER(carrera) IN (LOWER($1), LOWER($2)) \
+         AND (email ILIKE $3 ESCAPE '!')"
+    );
+}
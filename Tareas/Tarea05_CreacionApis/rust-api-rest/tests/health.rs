@@ -0,0 +1,186 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tower::ServiceExt;
+
+use rust_api_rest::{
+    database::{AlumnoFilter, FieldSet, PageStart, Sort},
+    error::AppError,
+    models::*,
+    repository::{AlumnoRepository, InMemoryAlumnoRepository},
+    routes,
+    state::AppState,
+};
+
+/// Repositorio cuya base de datos no responde a tiempo: la versión de migraciones nunca y
+/// el `SELECT 1` solo si `slow_ping`
+struct SlowRepository {
+    slow_ping: bool,
+}
+
+#[async_trait]
+impl AlumnoRepository for SlowRepository {
+    async fn list(
+        &self,
+        _: &AlumnoFilter,
+        _: &FieldSet,
+        _: &Sort,
+        _: &PageStart,
+        _: i64,
+    ) -> Result<Vec<PartialAlumno>, AppError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: &AlumnoFilter) -> Result<i64, AppError> {
+        Ok(0)
+    }
+
+    async fn batch(&self, _: i32, _: i64) -> Result<Vec<Alumno>, AppError> {
+        Ok(Vec::new())
+    }
+
+    async fn get(&self, _: i32) -> Result<Option<Alumno>, AppError> {
+        Ok(None)
+    }
+
+    async fn create(&self, _: &CreateAlumnoRequest) -> Result<Alumno, AppError> {
+        Err(AppError::Database(sqlx::Error::PoolTimedOut))
+    }
+
+    async fn update(
+        &self,
+        _: i32,
+        _: &UpdateAlumnoRequest,
+        _: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError> {
+        Ok(None)
+    }
+
+    async fn delete(&self, _: i32, _: Option<i32>) -> Result<bool, AppError> {
+        Ok(false)
+    }
+
+    async fn ping(&self) -> Result<(), AppError> {
+        if self.slow_ping {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+        Ok(())
+    }
+
+    async fn schema_version(&self) -> Result<Option<i64>, AppError> {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Ok(Some(3))
+    }
+}
+
+async fn get(app: Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn liveness_no_depende_de_la_base() {
+    let state = AppState::new(Arc::new(SlowRepository { slow_ping: true }));
+
+    let (status, body) = get(routes::health_routes(state), "/health/live").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+}
+
+#[tokio::test]
+async fn readiness_en_memoria_esta_lista_sin_pool() {
+    let state = AppState::new(Arc::new(InMemoryAlumnoRepository::new()));
+
+    let (status, body) = get(routes::health_routes(state), "/health/ready").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+    assert_eq!(body["database"]["healthy"], true);
+    assert!(body["pool"].is_null());
+    assert!(body["migration_version"].is_null());
+}
+
+#[tokio::test]
+async fn readiness_retorna_503_si_la_base_no_responde() {
+    let state = AppState::new(Arc::new(SlowRepository { slow_ping: true }))
+        .with_ping_timeout(Duration::from_millis(50));
+
+    let (status, body) = get(routes::health_routes(state), "/health/ready").await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "unavailable");
+    assert_eq!(body["database"]["healthy"], false);
+    assert_eq!(
+        body["database"]["error"],
+        "Sin respuesta de la base de datos en 50 ms"
+    );
+}
+
+#[tokio::test]
+async fn readiness_acota_la_lectura_de_la_version_de_migraciones() {
+    let state = AppState::new(Arc::new(SlowRepository { slow_ping: false }))
+        .with_ping_timeout(Duration::from_millis(50));
+
+    let started = std::time::Instant::now();
+    let (status, body) = get(routes::health_routes(state), "/health/ready").await;
+
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["database"]["healthy"], false);
+    assert_eq!(
+        body["database"]["error"],
+        "Sin respuesta de la base de datos en 50 ms"
+    );
+    assert!(body["migration_version"].is_null());
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn readiness_sobre_sqlite_reporta_pool_y_migraciones() {
+    use rust_api_rest::{
+        config::DatabaseSettings, database::create_sqlite_pool, repository::SqliteAlumnoRepository,
+    };
+
+    let pool = create_sqlite_pool(&DatabaseSettings::with_url("sqlite::memory:"), true)
+        .await
+        .unwrap();
+    let state = AppState::new(Arc::new(SqliteAlumnoRepository::new(pool)));
+
+    let (status, body) = get(routes::health_routes(state), "/health/ready").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["pool"]["size"], 1);
+    assert_eq!(body["pool"]["max_connections"], 1);
+    assert_eq!(body["migration_version"], 3);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn readiness_no_expone_el_error_de_la_base() {
+    use rust_api_rest::{
+        config::DatabaseSettings, database::create_sqlite_pool, repository::SqliteAlumnoRepository,
+    };
+
+    let pool = create_sqlite_pool(&DatabaseSettings::with_url("sqlite::memory:"), true)
+        .await
+        .unwrap();
+    pool.close().await;
+    let state = AppState::new(Arc::new(SqliteAlumnoRepository::new(pool)));
+
+    let (status, body) = get(routes::health_routes(state), "/health/ready").await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["database"]["healthy"], false);
+    assert_eq!(body["database"]["error"], "Base de datos no disponible");
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use axum::{Router, http::StatusCode, middleware};
+use serde_json::json;
+
+use rust_api_rest::{
+    metrics::{prometheus_handle, track_metrics},
+    repository::InMemoryAlumnoRepository,
+    routes,
+    state::AppState,
+};
+
+mod common;
+use common::{alumno, request, send};
+
+#[tokio::test]
+async fn metricas_de_requests_y_de_negocio() {
+    prometheus_handle();
+    let state = AppState::new(Arc::new(InMemoryAlumnoRepository::new()));
+    let app = Router::new()
+        .nest("/api", routes::api_routes(state.clone()))
+        .merge(routes::metrics_routes(state))
+        .route_layer(middleware::from_fn(track_metrics));
+
+    for (email, activo) in [("ana@email.com", true), ("luis@email.com", false)] {
+        let (status, body) = send(
+            &app,
+            "POST",
+            "/api/alumnos",
+            Some(alumno("Nombre", "Apellido", email)),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        if !activo {
+            let id = body["data"]["id"].clone();
+            send(
+                &app,
+                "PATCH",
+                &format!("/api/alumnos/{id}"),
+                Some(json!({"activo": false})),
+            )
+            .await;
+        }
+    }
+    send(&app, "GET", "/api/alumnos/999", None).await;
+
+    let response = request(&app, "GET", "/metrics", &[], None).await;
+    assert_eq!(response.status, StatusCode::OK);
+    let metrics = response.text();
+
+    // La ruta se reporta como plantilla, con el código de estado como etiqueta
+    assert!(
+        metrics
+            .contains(r#"http_requests_total{method="POST",path="/api/alumnos",status="200"} 2"#)
+    );
+    assert!(
+        metrics.contains(
+            r#"http_requests_total{method="GET",path="/api/alumnos/{id}",status="404"} 1"#
+        )
+    );
+    assert!(metrics.contains(
+        r#"http_request_duration_seconds_bucket{method="PATCH",path="/api/alumnos/{id}",status="200",le="+Inf"} 1"#
+    ));
+
+    assert!(metrics.contains("alumnos_registrados 2"));
+    assert!(metrics.contains("alumnos_activos 1"));
+
+    // Sin pool (almacenamiento en memoria) no hay gauges del pool
+    assert!(!metrics.contains("db_pool_connections"));
+}
@@ -0,0 +1,203 @@
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    extract::State,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+};
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use serde_json::Value;
+use tower::{ServiceBuilder, ServiceExt};
+use tower_http::trace::TraceLayer;
+use tracing_subscriber::layer::SubscriberExt;
+
+use rust_api_rest::{
+    config::{DatabaseSettings, OtelSettings, OtlpProtocol},
+    repository,
+    request_id::request_id,
+    routes,
+    state::AppState,
+    telemetry::{RecordResponse, RequestSpan, otel_layer, tracer_provider},
+};
+
+const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+/// Collector OTLP/HTTP en proceso que guarda cada exportación recibida
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<Value>>>);
+
+impl Collector {
+    async fn serve(&self, listener: tokio::net::TcpListener) {
+        async fn receive(State(collector): State<Collector>, body: Bytes) -> StatusCode {
+            collector
+                .0
+                .lock()
+                .unwrap()
+                .push(serde_json::from_slice(&body).unwrap());
+            StatusCode::OK
+        }
+
+        let app = Router::new()
+            .route("/v1/traces", post(receive))
+            .with_state(self.clone());
+        axum::serve(listener, app).await.unwrap();
+    }
+
+    /// Spans recibidos de una traza, aplanados desde resourceSpans/scopeSpans
+    fn spans(&self, trace_id: &str) -> Vec<Value> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .flat_map(|export| {
+                export["resourceSpans"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .flat_map(|resource| {
+                resource["scopeSpans"]
+                    .as_array()
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .flat_map(|scope| scope["spans"].as_array().cloned().unwrap_or_default())
+            .filter(|span| span["traceId"] == trace_id)
+            .collect()
+    }
+}
+
+fn span<'a>(spans: &'a [Value], name: &str) -> &'a Value {
+    spans
+        .iter()
+        .find(|span| span["name"] == name)
+        .unwrap_or_else(|| panic!("no se exportó el span {name}: {spans:#?}"))
+}
+
+fn attribute(span: &Value, key: &str) -> Value {
+    span["attributes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|attribute| attribute["key"] == key)
+        .map(|attribute| attribute["value"].clone())
+        .unwrap_or_else(|| panic!("el span no tiene el atributo {key}: {span:#?}"))
+}
+
+/// Collector y proveedor compartidos por los tests del archivo. El subscriber tiene que
+/// ser global: SQLite ejecuta las consultas en su propio hilo.
+fn telemetry() -> &'static (Collector, SdkTracerProvider) {
+    static TELEMETRY: OnceLock<(Collector, SdkTracerProvider)> = OnceLock::new();
+
+    TELEMETRY.get_or_init(|| {
+        let collector = Collector::default();
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr: SocketAddr = listener.local_addr().unwrap();
+        listener.set_nonblocking(true).unwrap();
+
+        // El collector corre en su propio runtime para sobrevivir a cada test
+        let server = collector.clone();
+        std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async move {
+                    let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                    server.serve(listener).await
+                })
+        });
+
+        let provider = tracer_provider(&OtelSettings {
+            enabled: true,
+            endpoint: format!("http://{addr}/v1/traces"),
+            protocol: OtlpProtocol::Json,
+            ..OtelSettings::default()
+        })
+        .unwrap();
+        tracing::subscriber::set_global_default(
+            tracing_subscriber::registry().with(otel_layer(&provider)),
+        )
+        .unwrap();
+
+        (collector, provider)
+    })
+}
+
+/// Envía una request con `traceparent` a través de la misma pila de capas que `main`
+async fn traced_request(database_url: &str, uri: &str, trace_id: &str) -> StatusCode {
+    let alumnos = repository::connect(&DatabaseSettings::with_url(database_url), true)
+        .await
+        .unwrap();
+    let app = Router::new()
+        .nest("/api", routes::api_routes(AppState::new(alumnos)))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn(request_id))
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(RequestSpan::new(&[]))
+                        .on_response(RecordResponse),
+                ),
+        );
+
+    app.oneshot(
+        Request::builder()
+            .uri(uri)
+            .header("traceparent", format!("00-{trace_id}-{PARENT_SPAN_ID}-01"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await
+    .unwrap()
+    .status()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn continua_la_traza_del_cliente_con_spans_de_request_y_handler() {
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929b0e0e4736";
+    let (collector, provider) = telemetry();
+
+    let status = traced_request("memory:", "/api/alumnos/1", TRACE_ID).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    provider.force_flush().unwrap();
+
+    let spans = collector.spans(TRACE_ID);
+    let request = span(&spans, "request");
+    let handler = span(&spans, "get_alumno");
+
+    assert_eq!(request["parentSpanId"], PARENT_SPAN_ID);
+    assert_eq!(handler["parentSpanId"], request["spanId"]);
+    assert_eq!(attribute(request, "path")["stringValue"], "/api/alumnos/1");
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test(flavor = "multi_thread")]
+async fn cada_consulta_sql_es_un_span_hijo_del_handler() {
+    const TRACE_ID: &str = "0af7651916cd43dd8448eb211c80319c";
+    let (collector, provider) = telemetry();
+
+    let status = traced_request("sqlite::memory:", "/api/alumnos/1", TRACE_ID).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    provider.force_flush().unwrap();
+
+    let spans = collector.spans(TRACE_ID);
+    let handler = span(&spans, "get_alumno");
+    let query = spans
+        .iter()
+        .find(|span| span["parentSpanId"] == handler["spanId"])
+        .unwrap_or_else(|| panic!("la consulta no tiene span: {spans:#?}"));
+
+    assert_eq!(
+        attribute(query, "db.statement")["stringValue"],
+        "SELECT * FROM alumnos WHERE id = ?"
+    );
+    assert!(!attribute(query, "db.rows_returned").is_null());
+    assert!(query["endTimeUnixNano"] != query["startTimeUnixNano"]);
+}
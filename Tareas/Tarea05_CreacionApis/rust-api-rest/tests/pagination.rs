@@ -0,0 +1,447 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    http::{StatusCode, header},
+};
+use serde_json::{Value, json};
+
+use rust_api_rest::{
+    database::{AlumnoFilter, FieldSet, PageStart, Sort},
+    error::AppError,
+    models::*,
+    repository::{AlumnoRepository, InMemoryAlumnoRepository},
+    routes,
+    state::AppState,
+};
+
+mod common;
+use common::{alumno, request, send};
+
+async fn app_con_alumnos(cantidad: usize) -> Router {
+    let state = AppState::new(Arc::new(InMemoryAlumnoRepository::new()));
+    let app = Router::new().nest("/api", routes::api_routes(state));
+    for i in 1..=cantidad {
+        let alumno = alumno("Ana", "Pérez", &format!("alumno{i}@email.com"));
+        send(&app, "POST", "/api/alumnos", Some(alumno)).await;
+    }
+    app
+}
+
+fn ids(body: &Value) -> Vec<i64> {
+    body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|alumno| alumno["id"].as_i64().unwrap())
+        .collect()
+}
+
+fn cursor<'a>(body: &'a Value, field: &str) -> &'a str {
+    body[field]
+        .as_str()
+        .unwrap_or_else(|| panic!("falta {field}: {body}"))
+}
+
+#[tokio::test]
+async fn recorre_hacia_adelante_y_hacia_atras() {
+    let app = app_con_alumnos(5).await;
+
+    let (_, first) = send(&app, "GET", "/api/alumnos?limit=2", None).await;
+    assert_eq!(ids(&first), [5, 4]);
+    assert_eq!(first["total"], 5);
+
+    let uri = format!(
+        "/api/alumnos?limit=2&after={}",
+        cursor(&first, "next_cursor")
+    );
+    let (_, second) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&second), [3, 2]);
+
+    let uri = format!(
+        "/api/alumnos?limit=2&after={}",
+        cursor(&second, "next_cursor")
+    );
+    let (_, last) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&last), [1]);
+    assert!(last.get("next_cursor").is_none());
+
+    let uri = format!(
+        "/api/alumnos?limit=2&before={}",
+        cursor(&last, "prev_cursor")
+    );
+    let (_, back) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&back), [3, 2]);
+
+    let uri = format!(
+        "/api/alumnos?limit=2&before={}",
+        cursor(&back, "prev_cursor")
+    );
+    let (_, start) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&start), [5, 4]);
+    assert!(start.get("prev_cursor").is_none());
+}
+
+#[tokio::test]
+async fn el_modo_por_paginas_sigue_disponible() {
+    let app = app_con_alumnos(3).await;
+
+    let (status, body) = send(&app, "GET", "/api/alumnos?page=2&limit=2", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), [1]);
+    assert!(body.get("next_cursor").is_none());
+    assert!(body["prev_cursor"].is_string());
+}
+
+#[tokio::test]
+async fn cursores_invalidos_o_combinados_son_rechazados() {
+    let app = app_con_alumnos(3).await;
+    let (_, first) = send(&app, "GET", "/api/alumnos?limit=1", None).await;
+    let next = cursor(&first, "next_cursor");
+
+    for uri in [
+        "/api/alumnos?after=no-es-un-cursor".to_string(),
+        format!("/api/alumnos?page=2&after={next}"),
+        format!("/api/alumnos?after={next}&before={next}"),
+    ] {
+        let (status, body) = send(&app, "GET", &uri, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        assert_eq!(body["success"], false);
+    }
+}
+
+#[tokio::test]
+async fn ordena_por_columnas_permitidas_con_desempate_por_id() {
+    let app = app_con_alumnos(0).await;
+    for (i, semestre) in [3, 1, 3, 2, 1].into_iter().enumerate() {
+        let mut alumno = alumno("Ana", "Pérez", &format!("alumno{i}@email.com"));
+        alumno["semestre"] = json!(semestre);
+        send(&app, "POST", "/api/alumnos", Some(alumno)).await;
+    }
+
+    // Semestre ascendente y, entre empates, id ascendente
+    let (_, first) = send(&app, "GET", "/api/alumnos?sort=semestre&limit=2", None).await;
+    assert_eq!(ids(&first), [2, 5]);
+
+    let uri = format!(
+        "/api/alumnos?sort=semestre&limit=2&after={}",
+        cursor(&first, "next_cursor")
+    );
+    let (_, second) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&second), [4, 1]);
+
+    let uri = format!(
+        "/api/alumnos?sort=semestre&limit=2&after={}",
+        cursor(&second, "next_cursor")
+    );
+    let (_, last) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&last), [3]);
+
+    let uri = format!(
+        "/api/alumnos?sort=semestre&limit=2&before={}",
+        cursor(&last, "prev_cursor")
+    );
+    let (_, back) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&back), [4, 1]);
+
+    let (_, desc) = send(&app, "GET", "/api/alumnos?sort=-semestre", None).await;
+    assert_eq!(ids(&desc), [3, 1, 4, 5, 2]);
+}
+
+#[tokio::test]
+async fn ordena_texto_sin_mayusculas_ni_acentos() {
+    let app = app_con_alumnos(0).await;
+    for (i, apellido) in ["Zapata", "castro", "Álvarez", "Benítez"]
+        .into_iter()
+        .enumerate()
+    {
+        let alumno = alumno("Ana", apellido, &format!("alumno{i}@email.com"));
+        send(&app, "POST", "/api/alumnos", Some(alumno)).await;
+    }
+
+    // Igual que la collation de MySQL: Álvarez va primero, no al final
+    let (_, body) = send(&app, "GET", "/api/alumnos?sort=apellido", None).await;
+    let apellidos: Vec<&str> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|alumno| alumno["apellido"].as_str().unwrap())
+        .collect();
+    assert_eq!(apellidos, ["Álvarez", "Benítez", "castro", "Zapata"]);
+
+    // El cursor conserva ese orden
+    let (_, first) = send(&app, "GET", "/api/alumnos?sort=apellido&limit=2", None).await;
+    let uri = format!(
+        "/api/alumnos?sort=apellido&limit=2&after={}",
+        cursor(&first, "next_cursor")
+    );
+    let (_, second) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&first), [3, 4]);
+    assert_eq!(ids(&second), [2, 1]);
+}
+
+#[tokio::test]
+async fn filtra_carrera_y_dominio_sin_mayusculas_ni_acentos() {
+    let app = app_con_alumnos(0).await;
+    let mut ingenieria = alumno("Ana", "Pérez", "ana@Unión.edu.mx");
+    ingenieria["carrera"] = json!("Ingeniería Civil");
+    send(&app, "POST", "/api/alumnos", Some(ingenieria)).await;
+    send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Luis", "Gómez", "luis@email.com")),
+    )
+    .await;
+
+    // Igual que la collation de MySQL: los acentos no cuentan
+    for uri in [
+        "/api/alumnos?carrera=ingenieria%20civil",
+        "/api/alumnos?carrera=INGENIERÍA%20CIVIL",
+        "/api/alumnos?email_dominio=union.edu.mx",
+    ] {
+        let (_, body) = send(&app, "GET", uri, None).await;
+        assert_eq!(ids(&body), [1], "{uri}");
+    }
+}
+
+#[tokio::test]
+async fn orden_invalido_o_distinto_al_del_cursor_es_rechazado() {
+    let app = app_con_alumnos(3).await;
+    let (_, first) = send(&app, "GET", "/api/alumnos?limit=1", None).await;
+    let next = cursor(&first, "next_cursor");
+
+    for uri in [
+        "/api/alumnos?sort=password".to_string(),
+        "/api/alumnos?sort=apellido,-apellido".to_string(),
+        format!("/api/alumnos?sort=apellido&after={next}"),
+    ] {
+        let (status, body) = send(&app, "GET", &uri, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        assert_eq!(body["success"], false);
+    }
+}
+
+#[tokio::test]
+async fn rangos_invertidos_son_rechazados() {
+    let app = app_con_alumnos(1).await;
+
+    let (status, body) = send(
+        &app,
+        "GET",
+        "/api/alumnos?edad_min=30&edad_max=20&promedio_min=9&promedio_max=8",
+        None,
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let errors = body["errors"].as_array().unwrap();
+    assert!(errors.contains(&json!("edad_min no puede ser mayor que edad_max")));
+    assert!(errors.contains(&json!("promedio_min no puede ser mayor que promedio_max")));
+
+    let (status, body) = send(
+        &app,
+        "GET",
+        "/api/alumnos?edad_min=18&registrado_hasta=2100-01-01T00:00:00Z",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 1);
+}
+
+#[tokio::test]
+async fn metadatos_y_enlaces_del_modo_por_paginas() {
+    let app = app_con_alumnos(5).await;
+
+    let response = request(
+        &app,
+        "GET",
+        "/api/alumnos?activo=true&page=2&limit=2&sort=-id",
+        &[],
+        None,
+    )
+    .await;
+    let link = response.header(header::LINK.as_str()).unwrap();
+    let body = response.json();
+
+    assert_eq!(ids(&body), [3, 2]);
+    assert_eq!(body["page"], 2);
+    assert_eq!(body["limit"], 2);
+    assert_eq!(body["total"], 5);
+    assert_eq!(body["total_pages"], 3);
+    assert_eq!(body["has_next"], true);
+    assert_eq!(body["has_prev"], true);
+
+    // Los filtros, el orden y el límite se conservan en todos los enlaces
+    let links = &body["links"];
+    assert_eq!(
+        links["self"],
+        "/api/alumnos?activo=true&page=2&limit=2&sort=-id"
+    );
+    assert_eq!(
+        links["first"],
+        "/api/alumnos?activo=true&limit=2&sort=-id&page=1"
+    );
+    assert_eq!(
+        links["prev"],
+        "/api/alumnos?activo=true&limit=2&sort=-id&page=1"
+    );
+    assert_eq!(
+        links["next"],
+        "/api/alumnos?activo=true&limit=2&sort=-id&page=3"
+    );
+    assert_eq!(
+        links["last"],
+        "/api/alumnos?activo=true&limit=2&sort=-id&page=3"
+    );
+
+    assert!(link.contains("</api/alumnos?activo=true&limit=2&sort=-id&page=3>; rel=\"next\""));
+    assert!(link.contains("</api/alumnos?activo=true&limit=2&sort=-id&page=1>; rel=\"prev\""));
+    assert_eq!(link.matches("rel=").count(), 5);
+}
+
+#[tokio::test]
+async fn enlaces_del_modo_por_cursor() {
+    let app = app_con_alumnos(3).await;
+
+    let (_, first) = send(&app, "GET", "/api/alumnos?limit=2", None).await;
+    assert_eq!(first["has_prev"], false);
+    assert!(first["links"].get("prev").is_none());
+
+    assert_eq!(first["links"]["next"], "/api/alumnos?limit=2&page=2");
+
+    let uri = format!(
+        "/api/alumnos?limit=2&after={}",
+        cursor(&first, "next_cursor")
+    );
+    let (_, second) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&second), [1]);
+    assert!(second.get("page").is_none());
+    assert_eq!(second["has_next"], false);
+    assert_eq!(second["total_pages"], 2);
+    assert!(second["links"].get("next").is_none());
+    assert_eq!(
+        second["links"]["prev"],
+        format!(
+            "/api/alumnos?limit=2&before={}",
+            cursor(&second, "prev_cursor")
+        )
+    );
+}
+
+/// Repositorio cuyo COUNT falla
+struct CountFails;
+
+#[async_trait]
+impl AlumnoRepository for CountFails {
+    async fn list(
+        &self,
+        _: &AlumnoFilter,
+        _: &FieldSet,
+        _: &Sort,
+        _: &PageStart,
+        _: i64,
+    ) -> Result<Vec<PartialAlumno>, AppError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: &AlumnoFilter) -> Result<i64, AppError> {
+        Err(AppError::Database(sqlx::Error::PoolTimedOut))
+    }
+
+    async fn batch(&self, _: i32, _: i64) -> Result<Vec<Alumno>, AppError> {
+        Ok(Vec::new())
+    }
+
+    async fn get(&self, _: i32) -> Result<Option<Alumno>, AppError> {
+        Ok(None)
+    }
+
+    async fn create(&self, _: &CreateAlumnoRequest) -> Result<Alumno, AppError> {
+        unimplemented!()
+    }
+
+    async fn update(
+        &self,
+        _: i32,
+        _: &UpdateAlumnoRequest,
+        _: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError> {
+        Ok(None)
+    }
+
+    async fn delete(&self, _: i32, _: Option<i32>) -> Result<bool, AppError> {
+        Ok(false)
+    }
+}
+
+#[tokio::test]
+async fn un_error_en_el_conteo_no_se_reporta_como_cero() {
+    let app = Router::new().nest(
+        "/api",
+        routes::api_routes(AppState::new(Arc::new(CountFails))),
+    );
+
+    let (status, body) = send(&app, "GET", "/api/alumnos", None).await;
+
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body["success"], false);
+    assert!(body.get("total").is_none());
+}
+
+#[tokio::test]
+async fn fields_devuelve_solo_los_campos_pedidos() {
+    let app = app_con_alumnos(3).await;
+
+    // El orden por fecha_registro se usa para los cursores, pero no se devuelve
+    let (status, body) = send(
+        &app,
+        "GET",
+        "/api/alumnos?fields=id,nombre,apellido&limit=2",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    for alumno in body["data"].as_array().unwrap() {
+        let mut keys: Vec<_> = alumno.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, ["apellido", "id", "nombre"]);
+    }
+
+    let uri = format!(
+        "/api/alumnos?fields=nombre&limit=2&after={}",
+        cursor(&body, "next_cursor")
+    );
+    let (_, next) = send(&app, "GET", &uri, None).await;
+    assert_eq!(next["data"], json!([{"nombre": "Ana"}]));
+
+    let (status, body) = send(&app, "GET", "/api/alumnos/2?fields=email", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!({"email": "alumno2@email.com"}));
+
+    let (_, body) = send(&app, "GET", "/api/alumnos/2", None).await;
+    assert_eq!(body["data"]["carrera"], "Ingeniería en Sistemas");
+    assert!(body["data"]["fecha_registro"].is_string());
+}
+
+#[tokio::test]
+async fn fields_con_campos_desconocidos_es_rechazado() {
+    let app = app_con_alumnos(1).await;
+
+    for uri in [
+        "/api/alumnos?fields=id,password",
+        "/api/alumnos/1?fields=contraseña",
+    ] {
+        let (status, body) = send(&app, "GET", uri, None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+        assert!(
+            body["message"]
+                .as_str()
+                .unwrap()
+                .contains("Campos permitidos")
+        );
+    }
+}
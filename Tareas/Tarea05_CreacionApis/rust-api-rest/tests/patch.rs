@@ -0,0 +1,315 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+use async_trait::async_trait;
+use axum::{Router, http::StatusCode};
+use serde_json::{Value, json};
+
+use rust_api_rest::{
+    database::{
+        AlumnoFilter, AlumnoPatch, FieldSet, JSON_PATCH_CONTENT_TYPE, MERGE_PATCH_CONTENT_TYPE,
+        PageStart, Sort,
+    },
+    error::AppError,
+    models::*,
+    repository::{AlumnoRepository, InMemoryAlumnoRepository},
+    routes,
+    state::AppState,
+};
+
+mod common;
+use common::{app_con_alumno, crear_ana, request, send, send_with_headers};
+
+async fn patch(app: &Router, content_type: &str, body: Value) -> (StatusCode, Value) {
+    let headers = [("content-type", content_type)];
+    let (status, _, body) =
+        send_with_headers(app, "PATCH", "/api/alumnos/1", &headers, Some(body)).await;
+    (status, body)
+}
+
+#[tokio::test]
+async fn merge_patch_cambia_solo_los_campos_presentes() {
+    let app = app_con_alumno(false).await;
+
+    let (status, body) = patch(
+        &app,
+        MERGE_PATCH_CONTENT_TYPE,
+        json!({"semestre": 6, "activo": false}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["semestre"], 6);
+    assert_eq!(body["data"]["activo"], false);
+    assert_eq!(body["data"]["promedio"], 8.5);
+
+    // application/json con parámetros también es un merge patch
+    let (status, body) = patch(
+        &app,
+        "application/json; charset=utf-8",
+        json!({"promedio": 9}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["promedio"], 9.0);
+
+    // Un patch vacío no cambia nada
+    let (status, body) = patch(&app, MERGE_PATCH_CONTENT_TYPE, json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["semestre"], 6);
+}
+
+#[tokio::test]
+async fn merge_patch_distingue_omitir_de_null() {
+    let app = app_con_alumno(false).await;
+
+    for (patch_body, message) in [
+        (
+            json!({"carrera": null}),
+            "El campo carrera es obligatorio: no admite null ni se puede quitar",
+        ),
+        (json!({"password": "x"}), "Campo desconocido: password"),
+        (json!({"id": 7}), "El campo id es de solo lectura"),
+        (
+            json!({"fecha_registro": "2020-01-01T00:00:00Z"}),
+            "El campo fecha_registro es de solo lectura",
+        ),
+    ] {
+        let (status, body) = patch(&app, MERGE_PATCH_CONTENT_TYPE, patch_body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["message"], message);
+    }
+
+    // Las reglas de UpdateAlumnoRequest también aplican
+    let (status, body) = patch(&app, MERGE_PATCH_CONTENT_TYPE, json!({"edad": 10})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"][0], "Edad debe estar entre 16 y 65 años");
+
+    let (status, _) = patch(&app, MERGE_PATCH_CONTENT_TYPE, json!({"edad": "veinte"})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // El alumno no cambió
+    let (_, body) = send(&app, "GET", "/api/alumnos/1", None).await;
+    assert_eq!(body["data"]["carrera"], "Ingeniería en Sistemas");
+    assert_eq!(body["data"]["edad"], 21);
+}
+
+#[tokio::test]
+async fn json_patch_aplica_operaciones_en_orden() {
+    let app = app_con_alumno(false).await;
+
+    let (status, body) = patch(
+        &app,
+        JSON_PATCH_CONTENT_TYPE,
+        json!([
+            {"op": "test", "path": "/semestre", "value": 5},
+            {"op": "replace", "path": "/semestre", "value": 6},
+            {"op": "copy", "from": "/nombre", "path": "/apellido"}
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["semestre"], 6);
+    assert_eq!(body["data"]["apellido"], "Ana");
+
+    // Un test que no se cumple es un conflicto y no se aplica ninguna operación
+    let (status, body) = patch(
+        &app,
+        JSON_PATCH_CONTENT_TYPE,
+        json!([
+            {"op": "replace", "path": "/activo", "value": false},
+            {"op": "test", "path": "/semestre", "value": 5}
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(
+        body["message"],
+        "La operación test sobre '/semestre' no se cumplió"
+    );
+
+    let (status, _) = patch(
+        &app,
+        JSON_PATCH_CONTENT_TYPE,
+        json!([{"op": "remove", "path": "/email"}]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = patch(
+        &app,
+        JSON_PATCH_CONTENT_TYPE,
+        json!([{"op": "replace", "path": "/no/existe", "value": 1}]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, body) = send(&app, "GET", "/api/alumnos/1", None).await;
+    assert_eq!(body["data"]["activo"], true);
+}
+
+#[tokio::test]
+async fn patch_rechaza_otros_content_types_y_alumnos_inexistentes() {
+    let app = app_con_alumno(false).await;
+
+    let (status, _) = patch(&app, "text/plain", json!({"semestre": 6})).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let (status, _, _) = send_with_headers(
+        &app,
+        "PATCH",
+        "/api/alumnos/99",
+        &[("content-type", MERGE_PATCH_CONTENT_TYPE)],
+        Some(json!({"semestre": 6})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    assert!(AlumnoPatch::parse(Some(JSON_PATCH_CONTENT_TYPE), b"{\"op\": 1}").is_err());
+    assert!(AlumnoPatch::parse(None, b"{}").is_err());
+}
+
+#[tokio::test]
+async fn put_requiere_la_representacion_completa() {
+    let app = app_con_alumno(false).await;
+
+    // Un PUT parcial ya no es válido
+    let (status, _) = send(&app, "PUT", "/api/alumnos/1", Some(json!({"semestre": 6}))).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, body) = send(
+        &app,
+        "PUT",
+        "/api/alumnos/1",
+        Some(json!({
+            "nombre": "Ana María",
+            "apellido": "Pérez",
+            "email": "ana.maria@email.com",
+            "edad": 22,
+            "carrera": "Medicina",
+            "semestre": 1
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["nombre"], "Ana María");
+    assert_eq!(body["data"]["carrera"], "Medicina");
+    assert_eq!(body["data"]["promedio"], 0.0);
+}
+
+/// Almacén en memoria en el que otra request sube el semestre justo después de cada una
+/// de las próximas `interferencias` lecturas de un alumno
+struct Concurrente {
+    inner: InMemoryAlumnoRepository,
+    interferencias: AtomicU32,
+}
+
+#[async_trait]
+impl AlumnoRepository for Concurrente {
+    async fn list(
+        &self,
+        filter: &AlumnoFilter,
+        fields: &FieldSet,
+        sort: &Sort,
+        start: &PageStart,
+        limit: i64,
+    ) -> Result<Vec<PartialAlumno>, AppError> {
+        self.inner.list(filter, fields, sort, start, limit).await
+    }
+
+    async fn count(&self, filter: &AlumnoFilter) -> Result<i64, AppError> {
+        self.inner.count(filter).await
+    }
+
+    async fn batch(&self, after: i32, limit: i64) -> Result<Vec<Alumno>, AppError> {
+        self.inner.batch(after, limit).await
+    }
+
+    async fn get(&self, id: i32) -> Result<Option<Alumno>, AppError> {
+        let alumno = self.inner.get(id).await?;
+        let pendientes = self.interferencias.load(Ordering::SeqCst);
+        if let Some(leido) = &alumno
+            && pendientes > 0
+        {
+            self.interferencias.store(pendientes - 1, Ordering::SeqCst);
+            let changes: UpdateAlumnoRequest =
+                serde_json::from_value(json!({"semestre": leido.semestre + 1})).unwrap();
+            self.inner.update(id, &changes, None).await?;
+        }
+        Ok(alumno)
+    }
+
+    async fn create(&self, alumno: &CreateAlumnoRequest) -> Result<Alumno, AppError> {
+        self.inner.create(alumno).await
+    }
+
+    async fn update(
+        &self,
+        id: i32,
+        changes: &UpdateAlumnoRequest,
+        version: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError> {
+        self.inner.update(id, changes, version).await
+    }
+
+    async fn delete(&self, id: i32, version: Option<i32>) -> Result<bool, AppError> {
+        self.inner.delete(id, version).await
+    }
+}
+
+#[tokio::test]
+async fn patch_no_pisa_una_escritura_concurrente() {
+    let repo = Arc::new(Concurrente {
+        inner: InMemoryAlumnoRepository::new(),
+        interferencias: AtomicU32::new(0),
+    });
+    let app = Router::new().nest("/api", routes::api_routes(AppState::new(repo.clone())));
+    crear_ana(&app).await;
+
+    // Sin If-Match el patch se vuelve a aplicar sobre el alumno con el semestre nuevo
+    repo.interferencias.store(1, Ordering::SeqCst);
+    let (status, body) = patch(&app, MERGE_PATCH_CONTENT_TYPE, json!({"nombre": "Anita"})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["nombre"], "Anita");
+    assert_eq!(body["data"]["semestre"], 6);
+    assert_eq!(body["data"]["version"], 3);
+
+    // Un test que se cumplía con los datos leídos se vuelve a evaluar y ya no se cumple
+    repo.interferencias.store(1, Ordering::SeqCst);
+    let (status, _) = patch(
+        &app,
+        JSON_PATCH_CONTENT_TYPE,
+        json!([
+            {"op": "test", "path": "/semestre", "value": 6},
+            {"op": "replace", "path": "/activo", "value": false}
+        ]),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Con If-Match el cliente decide: 412 en lugar de reintentar
+    repo.interferencias.store(1, Ordering::SeqCst);
+    let response = request(
+        &app,
+        "PATCH",
+        "/api/alumnos/1",
+        &[
+            ("content-type", MERGE_PATCH_CONTENT_TYPE),
+            ("if-match", "\"4\""),
+        ],
+        Some(json!({"nombre": "Ana"})),
+    )
+    .await;
+    assert_eq!(response.status, StatusCode::PRECONDITION_FAILED);
+
+    // Si el alumno cambia en cada intento, termina en 412
+    repo.interferencias.store(u32::MAX, Ordering::SeqCst);
+    let (status, _) = patch(&app, MERGE_PATCH_CONTENT_TYPE, json!({"nombre": "Ana"})).await;
+    assert_eq!(status, StatusCode::PRECONDITION_FAILED);
+
+    repo.interferencias.store(0, Ordering::SeqCst);
+    let (_, body) = send(&app, "GET", "/api/alumnos/1", None).await;
+    assert_eq!(body["data"]["nombre"], "Anita");
+    assert_eq!(body["data"]["activo"], true);
+}
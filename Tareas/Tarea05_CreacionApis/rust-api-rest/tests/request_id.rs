@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    response::Response,
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tower::ServiceExt;
+
+use rust_api_rest::{
+    repository::InMemoryAlumnoRepository,
+    request_id::{X_REQUEST_ID, request_id},
+    routes,
+    state::AppState,
+};
+
+fn app() -> Router {
+    let state = AppState::new(Arc::new(InMemoryAlumnoRepository::new()));
+    Router::new()
+        .nest("/api", routes::api_routes(state))
+        .layer(middleware::from_fn(request_id))
+}
+
+async fn get(uri: &str, request_id: Option<&str>) -> Response {
+    let mut request = Request::builder().uri(uri);
+    if let Some(id) = request_id {
+        request = request.header(X_REQUEST_ID, id);
+    }
+    app()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+fn header(response: &Response) -> String {
+    response.headers()[X_REQUEST_ID]
+        .to_str()
+        .unwrap()
+        .to_string()
+}
+
+async fn json(response: Response) -> Value {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn genera_un_id_si_el_cliente_no_lo_envia() {
+    let response = get("/api/alumnos", None).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let id = header(&response);
+    assert!(uuid::Uuid::parse_str(&id).is_ok(), "{id}");
+
+    // Las respuestas exitosas no cambian de forma
+    assert!(json(response).await.get("request_id").is_none());
+}
+
+#[tokio::test]
+async fn respeta_el_id_recibido_y_lo_incluye_en_el_error() {
+    let response = get("/api/alumnos/999", Some("frontend-7f3a.42")).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(header(&response), "frontend-7f3a.42");
+
+    let body = json(response).await;
+    assert_eq!(body["success"], false);
+    assert_eq!(body["request_id"], "frontend-7f3a.42");
+}
+
+#[tokio::test]
+async fn reemplaza_ids_invalidos() {
+    let largo = "a".repeat(200);
+    for invalido in ["con espacios", "<script>", largo.as_str()] {
+        let response = get("/api/alumnos/999", Some(invalido)).await;
+        let id = header(&response);
+
+        assert_ne!(id, invalido);
+        assert!(uuid::Uuid::parse_str(&id).is_ok());
+        assert_eq!(json(response).await["request_id"], id.as_str());
+    }
+}
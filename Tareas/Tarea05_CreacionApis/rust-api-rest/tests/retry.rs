@@ -0,0 +1,65 @@
+use std::{cell::Cell, time::Duration};
+
+use rust_api_rest::database::{RetryPolicy, retry_with_backoff};
+use tokio::time::Instant;
+
+fn policy(deadline_ms: u64) -> RetryPolicy {
+    RetryPolicy {
+        deadline: Duration::from_millis(deadline_ms),
+        initial_backoff: Duration::from_millis(5),
+        max_backoff: Duration::from_millis(20),
+    }
+}
+
+#[tokio::test]
+async fn reintenta_hasta_tener_exito() {
+    let attempts = Cell::new(0);
+
+    let result = retry_with_backoff(&policy(1_000), "operación", || {
+        attempts.set(attempts.get() + 1);
+        let attempt = attempts.get();
+        async move {
+            if attempt < 4 {
+                Err("conexión rechazada")
+            } else {
+                Ok(attempt)
+            }
+        }
+    })
+    .await;
+
+    assert_eq!(result, Ok(4));
+}
+
+#[tokio::test]
+async fn se_rinde_al_agotar_el_plazo() {
+    let attempts = Cell::new(0);
+    let start = Instant::now();
+
+    let result: Result<(), String> = retry_with_backoff(&policy(100), "operación", || {
+        attempts.set(attempts.get() + 1);
+        let attempt = attempts.get();
+        async move { Err(format!("fallo {attempt}")) }
+    })
+    .await;
+
+    let elapsed = start.elapsed();
+    assert_eq!(result, Err(format!("fallo {}", attempts.get())));
+    assert!(attempts.get() > 2);
+    assert!(elapsed >= Duration::from_millis(100));
+    assert!(elapsed < Duration::from_millis(500));
+}
+
+#[tokio::test]
+async fn plazo_cero_hace_un_solo_intento() {
+    let attempts = Cell::new(0);
+
+    let result: Result<(), &str> = retry_with_backoff(&policy(0), "operación", || {
+        attempts.set(attempts.get() + 1);
+        async { Err("fallo") }
+    })
+    .await;
+
+    assert_eq!(result, Err("fallo"));
+    assert_eq!(attempts.get(), 1);
+}
@@ -0,0 +1,259 @@
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+};
+use tower::ServiceExt;
+
+use rust_api_rest::{
+    database::{AlumnoFilter, FieldSet, PageStart, Sort},
+    error::AppError,
+    models::*,
+    repository::{AlumnoRepository, InMemoryAlumnoRepository},
+    routes,
+    shutdown::serve_with_shutdown,
+    state::AppState,
+};
+
+fn state() -> AppState {
+    AppState::new(Arc::new(InMemoryAlumnoRepository::new()))
+}
+
+/// Aplicación con un endpoint que tarda `delay` en responder
+fn slow_app(state: AppState, delay: Duration) -> Router {
+    Router::new()
+        .route(
+            "/lento",
+            get(move || async move {
+                tokio::time::sleep(delay).await;
+                "terminado"
+            }),
+        )
+        .merge(routes::health_routes(state))
+}
+
+/// Envía un GET por HTTP/1.1 y retorna la respuesta completa como texto
+async fn http_get(addr: SocketAddr, path: &str) -> std::io::Result<String> {
+    let mut stream = TcpStream::connect(addr).await?;
+    stream
+        .write_all(
+            format!("GET {path} HTTP/1.1\r\nHost: test\r\nConnection: close\r\n\r\n").as_bytes(),
+        )
+        .await?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await?;
+    Ok(response)
+}
+
+#[tokio::test]
+async fn readiness_retorna_503_al_iniciar_el_apagado() {
+    let state = state();
+    state.begin_shutdown();
+
+    let request = Request::builder()
+        .uri("/health/ready")
+        .body(Body::empty())
+        .unwrap();
+    let response = routes::health_routes(state).oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "shutting_down");
+    assert_eq!(body["database"]["healthy"], true);
+}
+
+#[tokio::test]
+async fn apagado_espera_las_requests_en_curso() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let state = state();
+    let (stop, stopped) = oneshot::channel::<()>();
+
+    let server = tokio::spawn(serve_with_shutdown(
+        listener,
+        slow_app(state.clone(), Duration::from_millis(300)),
+        state.clone(),
+        Duration::ZERO,
+        Duration::from_secs(5),
+        async {
+            stopped.await.ok();
+        },
+    ));
+
+    let in_flight = tokio::spawn(http_get(addr, "/lento"));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    stop.send(()).unwrap();
+
+    let response = in_flight.await.unwrap().unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+    assert!(response.ends_with("terminado"));
+
+    server.await.unwrap().unwrap();
+    assert!(state.is_shutting_down());
+
+    // El listener ya no acepta conexiones nuevas
+    assert!(http_get(addr, "/health/live").await.is_err());
+}
+
+#[tokio::test]
+async fn apagado_respeta_el_tiempo_maximo_de_drenado() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let state = state();
+    let (stop, stopped) = oneshot::channel::<()>();
+
+    let server = tokio::spawn(serve_with_shutdown(
+        listener,
+        slow_app(state.clone(), Duration::from_secs(30)),
+        state,
+        Duration::ZERO,
+        Duration::from_millis(200),
+        async {
+            stopped.await.ok();
+        },
+    ));
+
+    let _colgada = tokio::spawn(http_get(addr, "/lento"));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let start = Instant::now();
+    stop.send(()).unwrap();
+    server.await.unwrap().unwrap();
+
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn readiness_responde_503_antes_de_dejar_de_aceptar_conexiones() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let state = state();
+    let (stop, stopped) = oneshot::channel::<()>();
+
+    let server = tokio::spawn(serve_with_shutdown(
+        listener,
+        slow_app(state.clone(), Duration::ZERO),
+        state.clone(),
+        Duration::from_millis(500),
+        Duration::from_secs(5),
+        async {
+            stopped.await.ok();
+        },
+    ));
+
+    let response = http_get(addr, "/health/ready").await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+
+    let start = Instant::now();
+    stop.send(()).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Durante la espera se siguen aceptando conexiones y el balanceador ve el 503
+    let response = http_get(addr, "/health/ready").await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 503"), "{response}");
+    assert!(response.contains("shutting_down"), "{response}");
+    let response = http_get(addr, "/lento").await.unwrap();
+    assert!(response.ends_with("terminado"), "{response}");
+
+    server.await.unwrap().unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(500));
+    assert!(http_get(addr, "/health/live").await.is_err());
+}
+
+/// Repositorio vacío cuyo cierre no termina nunca, como un pool con una conexión colgada
+struct CloseHangs;
+
+#[async_trait]
+impl AlumnoRepository for CloseHangs {
+    async fn list(
+        &self,
+        _: &AlumnoFilter,
+        _: &FieldSet,
+        _: &Sort,
+        _: &PageStart,
+        _: i64,
+    ) -> Result<Vec<PartialAlumno>, AppError> {
+        Ok(Vec::new())
+    }
+
+    async fn count(&self, _: &AlumnoFilter) -> Result<i64, AppError> {
+        Ok(0)
+    }
+
+    async fn batch(&self, _: i32, _: i64) -> Result<Vec<Alumno>, AppError> {
+        Ok(Vec::new())
+    }
+
+    async fn get(&self, _: i32) -> Result<Option<Alumno>, AppError> {
+        Ok(None)
+    }
+
+    async fn create(&self, _: &CreateAlumnoRequest) -> Result<Alumno, AppError> {
+        Err(AppError::Database(sqlx::Error::PoolTimedOut))
+    }
+
+    async fn update(
+        &self,
+        _: i32,
+        _: &UpdateAlumnoRequest,
+        _: Option<i32>,
+    ) -> Result<Option<Alumno>, AppError> {
+        Ok(None)
+    }
+
+    async fn delete(&self, _: i32, _: Option<i32>) -> Result<bool, AppError> {
+        Ok(false)
+    }
+
+    async fn close(&self) {
+        std::future::pending::<()>().await;
+    }
+}
+
+#[tokio::test]
+async fn drenado_y_cierre_comparten_el_tiempo_maximo() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let state = AppState::new(Arc::new(CloseHangs));
+    let (stop, stopped) = oneshot::channel::<()>();
+
+    let server = tokio::spawn(serve_with_shutdown(
+        listener,
+        slow_app(state.clone(), Duration::from_secs(30)),
+        state,
+        Duration::ZERO,
+        Duration::from_millis(500),
+        async {
+            stopped.await.ok();
+        },
+    ));
+
+    let _colgada = tokio::spawn(http_get(addr, "/lento"));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Una request colgada y un cierre colgado no suman dos veces el tiempo máximo
+    let start = Instant::now();
+    stop.send(()).unwrap();
+    server.await.unwrap().unwrap();
+
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(500), "{elapsed:?}");
+    assert!(elapsed < Duration::from_millis(900), "{elapsed:?}");
+}
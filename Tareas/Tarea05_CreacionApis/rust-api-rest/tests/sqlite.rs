@@ -0,0 +1,791 @@
+#![cfg(feature = "sqlite")]
+
+use std::sync::Arc;
+
+use axum::{Router, http::StatusCode};
+use serde_json::{Value, json};
+
+use rust_api_rest::{
+    config::DatabaseSettings,
+    database::{AlumnoFilter, FieldSet, Identity, SearchQuery, create_sqlite_pool},
+    error::AppError,
+    models::{Alumno, CreateAlumnoRequest, PaginationQuery, PartialAlumno, UpdateAlumnoRequest},
+    repository::{AlumnoRepository, InMemoryAlumnoRepository, SqliteAlumnoRepository},
+    routes,
+    state::AppState,
+};
+
+mod common;
+use common::{alumno, request, send};
+
+async fn repository() -> SqliteAlumnoRepository {
+    let pool = create_sqlite_pool(&DatabaseSettings::with_url("sqlite::memory:"), true)
+        .await
+        .unwrap();
+    SqliteAlumnoRepository::new(pool)
+}
+
+async fn app() -> Router {
+    let state = AppState::new(Arc::new(repository().await));
+    Router::new().nest("/api", routes::api_routes(state))
+}
+
+#[tokio::test]
+async fn crud_completo_sobre_sqlite() {
+    let app = app().await;
+
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Andrés", "Mendoza", "andres@email.com")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let id = body["data"]["id"].as_i64().unwrap();
+    assert_eq!(body["data"]["activo"], true);
+    assert_eq!(body["data"]["promedio"], 8.5);
+
+    let (status, body) = send(&app, "GET", &format!("/api/alumnos/{id}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["email"], "andres@email.com");
+
+    let (status, body) = send(
+        &app,
+        "PATCH",
+        &format!("/api/alumnos/{id}"),
+        Some(json!({"semestre": 6, "activo": false})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["semestre"], 6);
+    assert_eq!(body["data"]["activo"], false);
+    assert_eq!(body["data"]["nombre"], "Andrés");
+
+    // PUT reemplaza el alumno completo: el promedio omitido vuelve a 0.0
+    let mut reemplazo = alumno("Andrés", "Mendoza Ruiz", "andres@email.com");
+    reemplazo.as_object_mut().unwrap().remove("promedio");
+    let (status, body) = send(&app, "PUT", &format!("/api/alumnos/{id}"), Some(reemplazo)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["apellido"], "Mendoza Ruiz");
+    assert_eq!(body["data"]["semestre"], 5);
+    assert_eq!(body["data"]["promedio"], 0.0);
+    assert_eq!(body["data"]["activo"], false);
+
+    let (status, _) = send(&app, "DELETE", &format!("/api/alumnos/{id}"), None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send(&app, "GET", &format!("/api/alumnos/{id}"), None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["message"], "Alumno no encontrado");
+}
+
+#[tokio::test]
+async fn email_duplicado_retorna_conflicto() {
+    let app = app().await;
+
+    send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Valeria", "Castro", "valeria@email.com")),
+    )
+    .await;
+    let (status, body) = send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Otra", "Persona", "VALERIA@email.com")),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["message"], "El email ya existe");
+}
+
+#[tokio::test]
+async fn listado_filtra_y_pagina() {
+    let app = app().await;
+
+    send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Diego", "Fernández", "diego@email.com")),
+    )
+    .await;
+    send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Carolina", "Morales", "carolina@email.com")),
+    )
+    .await;
+    let mut civil = alumno("Ricardo", "Vargas", "ricardo@email.com");
+    civil["carrera"] = json!("Ingeniería Civil");
+    send(&app, "POST", "/api/alumnos", Some(civil)).await;
+
+    let (_, body) = send(&app, "GET", "/api/alumnos?limit=2", None).await;
+    assert_eq!(body["total"], 3);
+    assert_eq!(body["data"].as_array().unwrap().len(), 2);
+
+    let (_, body) = send(&app, "GET", "/api/alumnos?search=mora", None).await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["data"][0]["nombre"], "Carolina");
+
+    let (_, body) = send(&app, "GET", "/api/alumnos?carrera=ingeniería%20civil", None).await;
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["data"][0]["apellido"], "Vargas");
+}
+
+#[tokio::test]
+async fn cursores_no_repiten_ni_saltan_alumnos() {
+    let app = app().await;
+    for i in 1..=5 {
+        let email = format!("alumno{i}@email.com");
+        send(
+            &app,
+            "POST",
+            "/api/alumnos",
+            Some(alumno("Ana", "Pérez", &email)),
+        )
+        .await;
+    }
+    let ids = |body: &Value| -> Vec<i64> {
+        body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|alumno| alumno["id"].as_i64().unwrap())
+            .collect()
+    };
+
+    // Todos se registran en el mismo segundo: el ID desempata el orden
+    let (_, first) = send(&app, "GET", "/api/alumnos?limit=2", None).await;
+    assert_eq!(ids(&first), [5, 4]);
+    assert!(first.get("prev_cursor").is_none());
+
+    // Un alta entre páginas no desplaza la siguiente
+    send(
+        &app,
+        "POST",
+        "/api/alumnos",
+        Some(alumno("Luis", "Gómez", "luis@email.com")),
+    )
+    .await;
+
+    let uri = format!(
+        "/api/alumnos?limit=2&after={}",
+        first["next_cursor"].as_str().unwrap()
+    );
+    let (status, second) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&second), [3, 2]);
+
+    let uri = format!(
+        "/api/alumnos?limit=2&before={}",
+        second["prev_cursor"].as_str().unwrap()
+    );
+    let (_, back) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&back), [5, 4]);
+    assert!(back["prev_cursor"].is_string(), "el alta nueva queda antes");
+}
+
+#[tokio::test]
+async fn ordena_por_columnas_del_cliente_y_pagina_con_cursor() {
+    let app = app().await;
+    for (apellido, promedio, email) in [
+        ("Vargas", 7.0, "a@email.com"),
+        ("Castro", 9.0, "b@email.com"),
+        ("Vargas", 9.5, "c@email.com"),
+        ("Castro", 9.0, "d@email.com"),
+    ] {
+        let mut nuevo = alumno("Ana", apellido, email);
+        nuevo["promedio"] = json!(promedio);
+        send(&app, "POST", "/api/alumnos", Some(nuevo)).await;
+    }
+    let ids = |body: &Value| -> Vec<i64> {
+        body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|alumno| alumno["id"].as_i64().unwrap())
+            .collect()
+    };
+
+    // Castro 9.0 (ids 2 y 4, desempate por id descendente), Vargas 9.5, Vargas 7.0
+    let (_, all) = send(&app, "GET", "/api/alumnos?sort=apellido,-promedio", None).await;
+    assert_eq!(ids(&all), [4, 2, 3, 1]);
+
+    let (_, first) = send(
+        &app,
+        "GET",
+        "/api/alumnos?sort=apellido,-promedio&limit=1",
+        None,
+    )
+    .await;
+    let uri = format!(
+        "/api/alumnos?sort=apellido,-promedio&limit=2&after={}",
+        first["next_cursor"].as_str().unwrap()
+    );
+    let (status, second) = send(&app, "GET", &uri, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&second), [2, 3]);
+
+    let uri = format!(
+        "/api/alumnos?sort=apellido,-promedio&limit=2&before={}",
+        second["next_cursor"].as_str().unwrap()
+    );
+    let (_, back) = send(&app, "GET", &uri, None).await;
+    assert_eq!(ids(&back), [4, 2]);
+}
+
+#[tokio::test]
+async fn fields_lee_solo_las_columnas_pedidas() {
+    let app = app().await;
+    for (nombre, email) in [
+        ("Diego", "diego@email.com"),
+        ("Carolina", "carolina@email.com"),
+    ] {
+        send(
+            &app,
+            "POST",
+            "/api/alumnos",
+            Some(alumno(nombre, "Morales", email)),
+        )
+        .await;
+    }
+
+    let (status, body) = send(
+        &app,
+        "GET",
+        "/api/alumnos?fields=nombre,promedio&sort=email",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!([
+            {"nombre": "Carolina", "promedio": 8.5},
+            {"nombre": "Diego", "promedio": 8.5}
+        ])
+    );
+
+    let repo = repository().await;
+    let creado = repo
+        .create(&CreateAlumnoRequest {
+            nombre: "Lucía".to_string(),
+            apellido: "Ramos".to_string(),
+            email: "lucia@email.com".to_string(),
+            edad: 22,
+            carrera: "Medicina".to_string(),
+            semestre: 3,
+            promedio: None,
+        })
+        .await
+        .unwrap();
+    let parcial = repo
+        .get_fields(creado.id, &FieldSet::parse(Some("id,activo")).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    // La versión se lee siempre, para el ETag
+    assert_eq!(
+        parcial,
+        PartialAlumno {
+            id: Some(creado.id),
+            activo: Some(true),
+            version: Some(1),
+            ..Default::default()
+        }
+    );
+}
+
+#[tokio::test]
+async fn entrada_hostil_se_busca_como_texto() {
+    let repo = repository().await;
+    for (nombre, apellido, email) in [
+        ("Conan", "O'Brien", "conan@email.com"),
+        ("Laura", "Cien%", "laura@email.com"),
+        ("Pedro", "Gómez", "pedro@email.com"),
+    ] {
+        repo.create(&CreateAlumnoRequest {
+            nombre: nombre.to_string(),
+            apellido: apellido.to_string(),
+            email: email.to_string(),
+            edad: 20,
+            carrera: "Ingeniería Civil".to_string(),
+            semestre: 1,
+            promedio: None,
+        })
+        .await
+        .unwrap();
+    }
+
+    let buscar = |texto: &str| AlumnoFilter {
+        search: Some(texto.to_string()),
+        ..Default::default()
+    };
+
+    // Las comillas no cierran el literal: se buscan tal cual
+    assert_eq!(repo.count(&buscar("O'Brien")).await.unwrap(), 1);
+    assert_eq!(repo.count(&buscar("' OR '1'='1")).await.unwrap(), 0);
+    assert_eq!(
+        repo.count(&buscar("'; DROP TABLE alumnos; --"))
+            .await
+            .unwrap(),
+        0
+    );
+
+    // Los signos solo separan palabras: los comodines de LIKE no buscan nada
+    assert_eq!(repo.count(&buscar("cien%")).await.unwrap(), 1);
+    assert_eq!(repo.count(&buscar("%")).await.unwrap(), 0);
+    assert_eq!(repo.count(&buscar("_")).await.unwrap(), 0);
+    assert_eq!(repo.count(&buscar("\"gomez\" OR *")).await.unwrap(), 0);
+
+    let carrera = AlumnoFilter {
+        carreras: vec!["x' OR carrera LIKE '%".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(repo.count(&carrera).await.unwrap(), 0);
+
+    // La tabla sigue intacta
+    assert_eq!(repo.count(&AlumnoFilter::default()).await.unwrap(), 3);
+}
+
+#[tokio::test]
+async fn filtros_por_rango_lista_y_dominio_coinciden_con_memoria() {
+    let sqlite = repository().await;
+    let memoria = InMemoryAlumnoRepository::new();
+    let repos: [&dyn AlumnoRepository; 2] = [&sqlite, &memoria];
+
+    for repo in repos {
+        for (email, edad, carrera, semestre, promedio) in [
+            ("ana@universidad.edu", 18, "Ingeniería Civil", 2, 9.0),
+            ("luis@UNIVERSIDAD.edu", 22, "Ingeniería en Sistemas", 6, 7.5),
+            ("sofia@gmail.com", 25, "Medicina", 8, 9.8),
+            ("pablo@otrauniversidad.edu", 20, "ingeniería civil", 4, 8.0),
+        ] {
+            repo.create(&CreateAlumnoRequest {
+                nombre: "Nombre".to_string(),
+                apellido: "Apellido".to_string(),
+                email: email.to_string(),
+                edad,
+                carrera: carrera.to_string(),
+                semestre,
+                promedio: Some(promedio),
+            })
+            .await
+            .unwrap();
+        }
+
+        let contar = |params: PaginationQuery| async move {
+            repo.count(&AlumnoFilter::from(&params)).await.unwrap()
+        };
+
+        let rango = PaginationQuery {
+            edad_min: Some(20),
+            edad_max: Some(25),
+            promedio_min: Some(8.0),
+            ..Default::default()
+        };
+        assert_eq!(contar(rango).await, 2);
+
+        let carreras = PaginationQuery {
+            carrera: Some("Ingeniería Civil,Medicina".to_string()),
+            semestre_max: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(contar(carreras).await, 2);
+
+        // El dominio es exacto: no incluye otrauniversidad.edu
+        let dominio = PaginationQuery {
+            email_dominio: Some("universidad.edu".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(contar(dominio).await, 2);
+
+        let ahora = chrono::Utc::now();
+        let desde = PaginationQuery {
+            registrado_desde: Some(ahora - chrono::Duration::hours(1)),
+            registrado_hasta: Some(ahora + chrono::Duration::hours(1)),
+            ..Default::default()
+        };
+        assert_eq!(contar(desde).await, 4);
+        let futuro = PaginationQuery {
+            registrado_desde: Some(ahora + chrono::Duration::hours(1)),
+            ..Default::default()
+        };
+        assert_eq!(contar(futuro).await, 0);
+    }
+}
+
+#[tokio::test]
+async fn restricciones_check_del_esquema() {
+    let repo = repository().await;
+
+    // Sin pasar por la validación del handler, la base rechaza la edad fuera de rango
+    let result = repo
+        .create(&CreateAlumnoRequest {
+            nombre: "Menor".to_string(),
+            apellido: "DeEdad".to_string(),
+            email: "menor@email.com".to_string(),
+            edad: 12,
+            carrera: "Ingeniería Civil".to_string(),
+            semestre: 1,
+            promedio: None,
+        })
+        .await;
+
+    assert!(matches!(result, Err(AppError::Database(_))));
+}
+
+#[tokio::test]
+async fn version_condiciona_update_y_delete() {
+    let repo = repository().await;
+    let creado = repo
+        .create(&CreateAlumnoRequest {
+            nombre: "Marta".to_string(),
+            apellido: "Vidal".to_string(),
+            email: "marta@email.com".to_string(),
+            edad: 23,
+            carrera: "Arquitectura".to_string(),
+            semestre: 4,
+            promedio: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(creado.version, 1);
+
+    let cambio = UpdateAlumnoRequest {
+        nombre: None,
+        apellido: None,
+        email: None,
+        edad: None,
+        carrera: None,
+        semestre: Some(5),
+        promedio: None,
+        activo: None,
+    };
+    let actualizado = repo
+        .update(creado.id, &cambio, Some(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actualizado.version, 2);
+
+    // La versión 1 ya no está vigente: ni el UPDATE ni el DELETE afectan filas
+    let result = repo.update(creado.id, &cambio, Some(1)).await;
+    assert!(matches!(result, Err(AppError::PreconditionFailed(_))));
+    let result = repo.delete(creado.id, Some(1)).await;
+    assert!(matches!(result, Err(AppError::PreconditionFailed(_))));
+
+    // Sin versión se actualiza igual y la versión sigue aumentando
+    let actualizado = repo
+        .update(creado.id, &cambio, None)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(actualizado.version, 3);
+
+    assert!(repo.delete(creado.id, Some(3)).await.unwrap());
+    assert!(
+        repo.update(creado.id, &cambio, Some(3))
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(!repo.delete(creado.id, Some(3)).await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn escrituras_simultaneas_no_se_pisan() {
+    // Una base en archivo permite varias conexiones a la vez (en memoria el pool usa una)
+    let path = std::env::temp_dir().join(format!("alumnos-concurrencia-{}.db", std::process::id()));
+    let url = format!("sqlite:{}", path.display());
+    let pool = create_sqlite_pool(&DatabaseSettings::with_url(url), true)
+        .await
+        .unwrap();
+    let repo = SqliteAlumnoRepository::new(pool.clone());
+
+    let creado = repo
+        .create(&CreateAlumnoRequest {
+            nombre: "Tomás".to_string(),
+            apellido: "Ibarra".to_string(),
+            email: "tomas@email.com".to_string(),
+            edad: 20,
+            carrera: "Contaduría".to_string(),
+            semestre: 1,
+            promedio: None,
+        })
+        .await
+        .unwrap();
+
+    // Ocho requests leyeron la versión 1 y guardan a la vez: solo una puede ganar
+    let tareas: Vec<_> = (1..=8)
+        .map(|semestre| {
+            let repo = repo.clone();
+            tokio::spawn(async move {
+                let cambio = UpdateAlumnoRequest {
+                    nombre: None,
+                    apellido: None,
+                    email: None,
+                    edad: None,
+                    carrera: None,
+                    semestre: Some(semestre),
+                    promedio: None,
+                    activo: None,
+                };
+                repo.update(creado.id, &cambio, Some(1)).await
+            })
+        })
+        .collect();
+
+    let mut ganadores = Vec::new();
+    for tarea in tareas {
+        match tarea.await.unwrap() {
+            Ok(Some(alumno)) => ganadores.push(alumno),
+            Err(AppError::PreconditionFailed(_)) => {}
+            otro => panic!("resultado inesperado: {otro:?}"),
+        }
+    }
+    assert_eq!(ganadores.len(), 1);
+    // La respuesta es exactamente lo que quedó guardado
+    let guardado = repo.get(creado.id).await.unwrap().unwrap();
+    assert_eq!(guardado.version, 2);
+    assert_eq!(json(&guardado), json(&ganadores[0]));
+
+    // Sin versión se aplican todos, en algún orden: cada uno devuelve lo que escribió él
+    // (su semestre, con una versión distinta) y no lo que otro escribió después
+    for ronda in 0..10 {
+        let tareas: Vec<_> = (1..=8)
+            .map(|semestre| {
+                let repo = repo.clone();
+                tokio::spawn(async move {
+                    let cambio = UpdateAlumnoRequest {
+                        nombre: None,
+                        apellido: None,
+                        email: None,
+                        edad: None,
+                        carrera: None,
+                        semestre: Some(semestre),
+                        promedio: None,
+                        activo: None,
+                    };
+                    let alumno = repo.update(creado.id, &cambio, None).await;
+                    (semestre, alumno.unwrap().unwrap())
+                })
+            })
+            .collect();
+
+        let mut escritos = Vec::new();
+        for tarea in tareas {
+            let (semestre, alumno) = tarea.await.unwrap();
+            assert_eq!(alumno.semestre, semestre, "ronda {ronda}");
+            escritos.push(alumno);
+        }
+        escritos.sort_by_key(|alumno| alumno.version);
+        let versiones: Vec<i32> = escritos.iter().map(|alumno| alumno.version).collect();
+        let primera = 3 + ronda * 8;
+        assert_eq!(versiones, (primera..primera + 8).collect::<Vec<_>>());
+        let guardado = repo.get(creado.id).await.unwrap().unwrap();
+        assert_eq!(json(&guardado), json(escritos.last().unwrap()));
+    }
+
+    // Un UPDATE y un DELETE (condicionado a la versión leída) simultáneos: el DELETE solo
+    // se aplica si llegó primero, y solo entonces el UPDATE no encuentra al alumno
+    for i in 0..20 {
+        let alumno = repo
+            .create(&CreateAlumnoRequest {
+                nombre: "Tomás".to_string(),
+                apellido: "Ibarra".to_string(),
+                email: format!("tomas{i}@email.com"),
+                edad: 20,
+                carrera: "Contaduría".to_string(),
+                semestre: 1,
+                promedio: None,
+            })
+            .await
+            .unwrap();
+        let cambio = UpdateAlumnoRequest {
+            nombre: Some("Tomás Andrés".to_string()),
+            apellido: None,
+            email: None,
+            edad: None,
+            carrera: None,
+            semestre: None,
+            promedio: None,
+            activo: None,
+        };
+        let (actualizado, eliminado) = tokio::join!(
+            repo.update(alumno.id, &cambio, None),
+            repo.delete(alumno.id, Some(alumno.version))
+        );
+        let guardado = repo.get(alumno.id).await.unwrap();
+        match (actualizado, eliminado) {
+            // El UPDATE llegó primero: el DELETE ve otra versión y el alumno sigue
+            (Ok(Some(actualizado)), Err(AppError::PreconditionFailed(_))) => {
+                assert_eq!(actualizado.nombre, "Tomás Andrés");
+                assert_eq!(actualizado.version, alumno.version + 1);
+                assert_eq!(json(&guardado.unwrap()), json(&actualizado));
+            }
+            // El DELETE llegó primero: el UPDATE ya no encuentra al alumno
+            (Ok(None), Ok(true)) => {
+                assert!(guardado.is_none());
+            }
+            otro => panic!("resultado inesperado: {otro:?}"),
+        }
+    }
+
+    pool.close().await;
+    for sufijo in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{sufijo}", path.display()));
+    }
+}
+
+// Representación JSON, para comparar alumnos completos
+fn json(alumno: &Alumno) -> Value {
+    serde_json::to_value(alumno).unwrap()
+}
+
+#[tokio::test]
+async fn metricas_del_pool() {
+    rust_api_rest::metrics::prometheus_handle();
+    let state = AppState::new(Arc::new(repository().await));
+    let app = routes::metrics_routes(state);
+
+    let metrics = request(&app, "GET", "/metrics", &[], None).await.text();
+
+    assert!(metrics.contains(r#"db_pool_connections{state="in_use"}"#));
+    assert!(metrics.contains(r#"db_pool_connections{state="idle"}"#));
+    assert!(metrics.contains("db_pool_max_connections 1"));
+    // La espera se registra en cada acquire de las consultas (aquí, las de los gauges)
+    assert!(metrics.contains(r#"db_pool_acquire_wait_seconds_bucket{le="0.001"}"#));
+    assert!(metrics.contains("db_pool_acquire_wait_seconds_count"));
+}
+
+#[tokio::test]
+async fn busqueda_de_texto_completo_ignora_acentos_y_ordena_por_relevancia() {
+    let sqlite = app().await;
+    let memoria = Router::new().nest(
+        "/api",
+        routes::api_routes(AppState::new(Arc::new(InMemoryAlumnoRepository::new()))),
+    );
+
+    for app in [&sqlite, &memoria] {
+        let mut medicina = alumno("Andrés", "Gómez", "agomez@universidad.edu");
+        medicina["carrera"] = json!("Medicina");
+        for body in [
+            alumno("Andres", "Andrade", "andres.andrade@email.com"),
+            medicina,
+            alumno("Lucía", "Pérez", "lucia@universidad.edu"),
+        ] {
+            send(app, "POST", "/api/alumnos", Some(body)).await;
+        }
+
+        // Sin acentos ni mayúsculas, por prefijo y en todas las columnas indexadas
+        let (_, body) = send(app, "GET", "/api/alumnos?search=ANDRÉS", None).await;
+        assert_eq!(body["total"], 2);
+        // Andrade coincide en nombre, apellido y email: es el más relevante
+        assert_eq!(body["data"][0]["apellido"], "Andrade");
+        assert_eq!(body["data"][1]["apellido"], "Gómez");
+        let scores: Vec<f64> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|alumno| alumno["score"].as_f64().unwrap())
+            .collect();
+        assert!(scores[0] > scores[1], "{scores:?}");
+
+        for (search, total) in [
+            ("andres gomez", 1),
+            ("gom", 1),
+            ("medicina", 1),
+            ("universidad", 2),
+            ("lucia perez", 1),
+            ("lucia gomez", 0),
+        ] {
+            let uri = format!("/api/alumnos?search={}", search.replace(' ', "%20"));
+            let (_, body) = send(app, "GET", &uri, None).await;
+            assert_eq!(body["total"], total, "search={search}");
+        }
+
+        // El cursor de una búsqueda recorre las coincidencias por relevancia
+        let (_, body) = send(app, "GET", "/api/alumnos?search=andres&limit=1", None).await;
+        assert_eq!(body["data"][0]["apellido"], "Andrade");
+        let next = body["next_cursor"].as_str().unwrap().to_string();
+        let (_, body) = send(
+            app,
+            "GET",
+            &format!("/api/alumnos?search=andres&limit=1&after={next}"),
+            None,
+        )
+        .await;
+        assert_eq!(body["data"][0]["apellido"], "Gómez");
+        assert_eq!(body["has_next"], false);
+
+        // Los campos parciales conservan la relevancia; sin búsqueda no hay score
+        let (_, body) = send(app, "GET", "/api/alumnos?search=lucia&fields=nombre", None).await;
+        assert_eq!(body["data"][0].as_object().unwrap().len(), 2);
+        assert!(body["data"][0]["score"].is_number());
+        let (_, body) = send(app, "GET", "/api/alumnos", None).await;
+        assert!(body["data"][0].get("score").is_none());
+        let (status, _) = send(app, "GET", "/api/alumnos?sort=-score", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // El índice sigue los cambios de la tabla
+        let id = body["data"][0]["id"].as_i64().unwrap();
+        send(
+            app,
+            "PATCH",
+            &format!("/api/alumnos/{id}"),
+            Some(json!({"apellido": "Zúñiga"})),
+        )
+        .await;
+        let (_, body) = send(app, "GET", "/api/alumnos?search=zuniga", None).await;
+        assert_eq!(body["total"], 1);
+        send(app, "DELETE", &format!("/api/alumnos/{id}"), None).await;
+        let (_, body) = send(app, "GET", "/api/alumnos?search=zuniga", None).await;
+        assert_eq!(body["total"], 0);
+    }
+}
+
+#[tokio::test]
+async fn candidatos_a_duplicado_usan_el_indice_de_busqueda() {
+    let repo = repository().await;
+    for (nombre, apellido, email) in [
+        ("Álvaro", "Núñez", "alvaro@email.com"),
+        ("Sofía", "Herrera", "sofia@email.com"),
+        ("Carlos", "Nuñes", "carlos@email.com"),
+        ("Inés", "Rojas", "nunez.ines@email.com"),
+    ] {
+        let payload: CreateAlumnoRequest =
+            serde_json::from_value(alumno(nombre, apellido, email)).unwrap();
+        repo.create(&payload).await.unwrap();
+    }
+
+    // Alvaro Nunez comparte "alv" con Álvaro y "nun" con Nuñes; Sofía no se lee, ni Inés,
+    // que solo tiene "nun" en el email
+    let identity = Identity {
+        nombre: "Alvaro",
+        apellido: "Nunez",
+        email: "anunez@email.com",
+        edad: 21,
+    };
+    let ids: Vec<i32> = repo
+        .matching(&identity.candidates_search())
+        .await
+        .unwrap()
+        .iter()
+        .map(|alumno| alumno.id)
+        .collect();
+    assert_eq!(ids, [1, 3]);
+
+    // La carrera tampoco cuenta: "ing" no trae a todos los de Ingeniería
+    let ingenieria = repo
+        .matching(&SearchQuery::names_starting_with(vec!["ing".to_string()]))
+        .await
+        .unwrap();
+    assert!(ingenieria.is_empty(), "{ingenieria:?}");
+}
@@ -0,0 +1,139 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+};
+use serde_json::Value;
+use tower::{ServiceBuilder, ServiceExt};
+use tower_http::trace::TraceLayer;
+use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt};
+
+use rust_api_rest::{
+    config::{LogFormat, LogSettings},
+    repository::InMemoryAlumnoRepository,
+    request_id::{X_REQUEST_ID, request_id},
+    routes,
+    state::AppState,
+    telemetry::{RecordResponse, RequestSpan, fmt_layer, redact_query},
+};
+
+/// Writer que acumula las líneas de log en memoria
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Capture {
+    fn lines(&self) -> Vec<Value> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+impl io::Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'w> MakeWriter<'w> for Capture {
+    type Writer = Capture;
+
+    fn make_writer(&'w self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+fn app() -> Router {
+    let state = AppState::new(Arc::new(InMemoryAlumnoRepository::new()));
+    Router::new().nest("/api", routes::api_routes(state)).layer(
+        ServiceBuilder::new()
+            .layer(middleware::from_fn(request_id))
+            .layer(
+                TraceLayer::new_for_http()
+                    .make_span_with(RequestSpan::new(&["email".to_string()]))
+                    .on_response(RecordResponse),
+            ),
+    )
+}
+
+#[test]
+fn redacta_solo_los_parametros_listados() {
+    let redact = ["email".to_string(), "token".to_string()];
+
+    assert_eq!(
+        redact_query("page=2&EMAIL=ana%40mail.com&token=abc&flag", &redact),
+        "page=2&EMAIL=[REDACTED]&token=[REDACTED]&flag"
+    );
+    assert_eq!(redact_query("nombre=Ana", &redact), "nombre=Ana");
+}
+
+#[test]
+fn por_defecto_se_redactan_la_busqueda_y_el_dominio() {
+    let redact = LogSettings::default().redact;
+
+    assert_eq!(
+        redact_query(
+            "search=ana%40mail.com&email_dominio=unam.mx&carrera=Derecho",
+            &redact
+        ),
+        "search=[REDACTED]&email_dominio=[REDACTED]&carrera=Derecho"
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn el_log_json_incluye_los_campos_de_la_request() {
+    let capture = Capture::default();
+    let subscriber =
+        tracing_subscriber::registry().with(fmt_layer(LogFormat::Json, capture.clone()));
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .uri("/api/alumnos?email=ana@mail.com&limit=5")
+                .header(X_REQUEST_ID, "req-42")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let lines = capture.lines();
+    let event = lines
+        .iter()
+        .find(|line| line["message"] == "respuesta enviada")
+        .expect("se registra la respuesta");
+
+    assert_eq!(event["level"], "INFO");
+    assert!(event["timestamp"].is_string());
+    assert_eq!(event["target"], "rust_api_rest::telemetry");
+
+    let span = &event["span"];
+    assert_eq!(span["method"], "GET");
+    assert_eq!(span["path"], "/api/alumnos");
+    assert_eq!(span["query"], "email=[REDACTED]&limit=5");
+    assert_eq!(span["request_id"], "req-42");
+    assert_eq!(span["status"], 200);
+    assert!(span["latency_ms"].is_u64());
+
+    // El email no aparece en ninguna línea
+    assert!(
+        lines
+            .iter()
+            .all(|line| !line.to_string().contains("ana@mail.com"))
+    );
+}